@@ -9,27 +9,75 @@ pub const INITIALIZER_ADDRESS: Pubkey =
 /// The percentage denominator for the fee percentages.
 pub const FEE_PERCENT_DENOMINATOR: u64 = 1000;
 
-/// The address for receiving the taxes fee.
-pub const TAXES_ADDRESSES: Pubkey =
+/// The taxes fee recipient to initialize the program with.
+pub const INITIAL_TAXES_ADDRESS: Pubkey =
     Pubkey::from_str_const("taxUg5xvdRz7Hc6Ps9fvNY5Coe1RxxH43cYnMHGHyaK");
-pub const TAXES_PERCENT: u64 = 75; // 7.5%
 
-/// The address for receiving the fines fee.
-pub const FINES_ADDRESSES: Pubkey =
+/// The taxes fee to initialize the program with.
+pub const INITIAL_TAXES_PERCENT: u64 = 75; // 7.5%
+
+/// The fines fee recipient to initialize the program with.
+pub const INITIAL_FINES_ADDRESS: Pubkey =
     Pubkey::from_str_const("Fine8MsMhoc5SaW9TjWzCc3RRDt6xD8m7RfTdrzaKLeP");
-pub const FINES_PERCENT: u64 = 75; // 7.5%
+
+/// The fines fee to initialize the program with.
+pub const INITIAL_FINES_PERCENT: u64 = 75; // 7.5%
+
+/// The share of taxes and fines recycled into the reward pool once emissions have ended, to
+/// initialize the program with.
+pub const INITIAL_RECYCLE_PERCENT: u64 = 500; // 50%
+
+/// The share of taxes paid to the referrer of a proof, to initialize the program with.
+pub const INITIAL_REFERRAL_PERCENT: u64 = 200; // 20%
+
+/// The number of seconds after a proof is opened during which its referrer is paid, to initialize
+/// the program with.
+pub const INITIAL_REFERRAL_WINDOW: i64 = ONE_MINUTE * 60 * 24 * 90; // 90 days
+
+/// The maximum number of seconds after a proof is opened during which its referrer is paid.
+pub const MAX_REFERRAL_WINDOW: i64 = ONE_MINUTE * 60 * 24 * 365; // 365 days
+
+/// The maximum combined taxes and fines fee.
+pub const MAX_FEES_PERCENT: u64 = 500; // 50%
+
+/// The basis point denominator for relayer fees and claim split weights.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// The maximum share of the miner reward a relayer may be paid.
+pub const MAX_RELAYER_FEE_BPS: u64 = 5_000; // 50%
+
+/// The maximum number of beneficiaries of a split claim.
+pub const MAX_CLAIM_SPLIT_BENEFICIARIES: usize = 8;
+
+/// The pause flag of the mine and mine relayed instructions.
+pub const PAUSE_MINE: u64 = 1 << 0;
+
+/// The pause flag of the claim and claim split instructions. Claims signed by a boost are exempt, so
+/// stakers can always withdraw from the boost program.
+pub const PAUSE_CLAIM: u64 = 1 << 1;
+
+/// The pause flag of the reset instruction.
+pub const PAUSE_RESET: u64 = 1 << 2;
+
+/// Every pause flag.
+pub const PAUSE_ALL: u64 = PAUSE_MINE | PAUSE_CLAIM | PAUSE_RESET;
 
 /// The base reward rate to intialize the program with.
-pub const INITIAL_BASE_REWARD_RATE: u64 = BASE_REWARD_RATE_MIN_THRESHOLD;
+pub const INITIAL_BASE_REWARD_RATE: u64 = INITIAL_BASE_REWARD_RATE_MIN_THRESHOLD;
 
-/// The minimum allowed base reward rate, at which point the min difficulty should be increased
-pub const BASE_REWARD_RATE_MIN_THRESHOLD: u64 = 2u64.pow(6);
+/// The minimum allowed base reward rate to initialize the program with, at which point the min
+/// difficulty should be increased.
+pub const INITIAL_BASE_REWARD_RATE_MIN_THRESHOLD: u64 = 2u64.pow(6);
 
-/// The maximum allowed base reward rate, at which point the min difficulty should be decreased.
-pub const BASE_REWARD_RATE_MAX_THRESHOLD: u64 = 2u64.pow(9);
+/// The maximum allowed base reward rate to initialize the program with, at which point the min
+/// difficulty should be decreased.
+pub const INITIAL_BASE_REWARD_RATE_MAX_THRESHOLD: u64 = 2u64.pow(9);
 
-/// The spam/liveness tolerance in seconds.
-pub const TOLERANCE: i64 = 5;
+/// The spam/liveness tolerance in seconds to initialize the program with.
+pub const INITIAL_TOLERANCE: i64 = 5;
+
+/// The maximum spam/liveness tolerance in seconds.
+pub const MAX_TOLERANCE: i64 = 30;
 
 /// The minimum difficulty to initialize the program with.
 pub const INITIAL_MIN_DIFFICULTY: u32 = 1;
@@ -44,11 +92,11 @@ pub const ONE_GARY: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
 /// The duration of one minute, in seconds.
 pub const ONE_MINUTE: i64 = 60;
 
-/// The number of minutes in a program epoch.
-pub const EPOCH_MINUTES: i64 = 15;
+/// The number of minutes in a program epoch to initialize the program with.
+pub const INITIAL_EPOCH_MINUTES: i64 = 15;
 
-/// The duration of a program epoch, in seconds.
-pub const EPOCH_DURATION: i64 = ONE_MINUTE * EPOCH_MINUTES;
+/// The maximum number of minutes in a program epoch.
+pub const MAX_EPOCH_MINUTES: i64 = 60 * 24;
 
 /// The maximum token supply (87B).
 pub const MAX_SUPPLY: u64 = ONE_GARY * 87_000_000_000;
@@ -59,15 +107,61 @@ pub const TARGET_DAY_REWARDS: u64 = ONE_GARY * 119_000_000;
 /// The target quantity of GARY to be mined per minute.
 pub const TARGET_MINUTE_REWARDS: u64 = TARGET_DAY_REWARDS / 24 / 60;
 
-/// The target quantity of GARY to be mined per epoch.
-pub const TARGET_EPOCH_REWARDS: u64 = TARGET_MINUTE_REWARDS * (EPOCH_MINUTES as u64);
+/// The target quantity of GARY to be mined per epoch, at the initial epoch length.
+pub const TARGET_EPOCH_REWARDS: u64 = TARGET_MINUTE_REWARDS * (INITIAL_EPOCH_MINUTES as u64);
 
-/// The number of bus accounts, for parallelizing mine operations.
+/// The number of bus accounts to initialize the program with, for parallelizing mine operations.
 pub const BUS_COUNT: usize = 8;
 
-/// The smoothing factor for reward rate changes. The reward rate cannot change by more or less
-/// than a factor of this constant from one epoch to the next.
-pub const SMOOTHING_FACTOR: u64 = 2;
+/// The maximum number of bus accounts. Reset must fit every bus in one legacy transaction, with
+/// room left for the compute budget and tip instructions.
+pub const MAX_BUS_COUNT: u64 = 16;
+
+/// The maximum number of bus accounts for which a reset still fits in one legacy transaction
+/// alongside a mine. Past this, the reset is sent on its own, or with a lookup table.
+pub const MAX_BUNDLED_RESET_BUS_COUNT: u64 = 8;
+
+/// The smoothing factor to initialize the program with. The reward rate cannot change by more or
+/// less than a factor of this value from one epoch to the next.
+pub const INITIAL_SMOOTHING_FACTOR: u64 = 2;
+
+/// The maximum smoothing factor for reward rate changes.
+pub const MAX_SMOOTHING_FACTOR: u64 = 16;
+
+/// The number of epochs averaged by the EMA retarget mode, to initialize the program with.
+pub const INITIAL_EMA_WINDOW: u64 = 8;
+
+/// The maximum number of epochs averaged by the EMA retarget mode.
+pub const MAX_EMA_WINDOW: u64 = 96;
+
+/// The crank reward paid for calling reset to initialize the program with.
+pub const INITIAL_RESET_REWARD: u64 = ONE_GARY * 100;
+
+/// The maximum crank reward paid for calling reset.
+pub const MAX_RESET_REWARD: u64 = ONE_GARY * 10_000;
+
+/// The number of difficulty buckets tracked per epoch. The last bucket counts every hash at or
+/// above its difficulty.
+pub const DIFFICULTY_HISTOGRAM_LEN: usize = 32;
+
+/// The number of past epochs kept in the epoch stats account.
+pub const EPOCH_STATS_LEN: usize = 32;
+
+/// The maximum number of additional miner keys a proof may authorize.
+pub const MAX_MINER_KEYS: usize = 8;
+
+/// The maximum number of addresses added to a lookup table by a single extend instruction, so
+/// each extension fits in one transaction.
+pub const LOOKUP_TABLE_EXTEND_LEN: usize = 20;
+
+/// The maximum size of a serialized transaction, including its signatures.
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// The current layout version of the config account.
+pub const CONFIG_VERSION: u64 = 8;
+
+/// The current layout version of proof accounts.
+pub const PROOF_VERSION: u64 = 1;
 
 /// The seed of the bus account PDA.
 pub const BUS: &[u8] = b"bus";
@@ -75,6 +169,9 @@ pub const BUS: &[u8] = b"bus";
 /// The seed of the config account PDA.
 pub const CONFIG: &[u8] = b"config";
 
+/// The seed of the epoch stats account PDA.
+pub const EPOCH_STATS: &[u8] = b"epoch_stats";
+
 /// The seed of the metadata account PDA.
 pub const METADATA: &[u8] = b"metadata";
 
@@ -104,7 +201,7 @@ pub const METADATA_URI: &str = "https://garygensler.org/metadata-v2.json";
 /// Program id for const pda derivations
 pub const PROGRAM_ID: [u8; 32] = unsafe { *(&crate::id() as *const Pubkey as *const [u8; 32]) };
 
-/// The addresses of the bus accounts.
+/// The addresses of the initial bus accounts. Use `sdk::bus_addresses` to find every bus.
 pub const BUS_ADDRESSES: [Pubkey; BUS_COUNT] = array_const_fn_init![const_bus_address; 8];
 
 /// Function to derive const bus addresses.
@@ -116,6 +213,10 @@ const fn const_bus_address(i: usize) -> Pubkey {
 pub const CONFIG_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[CONFIG], &PROGRAM_ID).0);
 
+/// The address of the epoch stats account.
+pub const EPOCH_STATS_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[EPOCH_STATS], &PROGRAM_ID).0);
+
 /// The address of the mint metadata account.
 pub const METADATA_ADDRESS: Pubkey = Pubkey::new_from_array(
     ed25519::derive_program_address(
@@ -0,0 +1,660 @@
+use std::str::FromStr;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use gary_drillx::Solution;
+use steel::*;
+
+use crate::{event::*, instruction::*};
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("The instruction does not belong to this program")]
+    InvalidProgramId,
+    #[error("The instruction data is invalid")]
+    InvalidData,
+    #[error("The instruction is missing required accounts")]
+    NotEnoughAccounts,
+}
+
+/// Decoded is a GARY instruction with its args parsed and its accounts named.
+///
+/// Sysvars and programs passed to the instruction are omitted.
+#[derive(Clone, Debug)]
+pub enum Decoded {
+    Claim {
+        signer: Pubkey,
+        beneficiary: Pubkey,
+        proof: Pubkey,
+        amount: u64,
+    },
+    Close {
+        signer: Pubkey,
+        proof: Pubkey,
+    },
+    Mine {
+        signer: Pubkey,
+        bus: Pubkey,
+        proof: Pubkey,
+        boost: Pubkey,
+        referrer_proof: Option<Pubkey>,
+        solution: Solution,
+    },
+    Open {
+        signer: Pubkey,
+        miner: Pubkey,
+        payer: Pubkey,
+        proof: Pubkey,
+        referrer_proof: Option<Pubkey>,
+    },
+    Reset {
+        signer: Pubkey,
+        busses: Vec<Pubkey>,
+    },
+    Update {
+        signer: Pubkey,
+        miner: Pubkey,
+        proof: Pubkey,
+    },
+    MigrateProof {
+        signer: Pubkey,
+        proof: Pubkey,
+    },
+    SetClaimDelegate {
+        signer: Pubkey,
+        proof: Pubkey,
+        delegate: Pubkey,
+        beneficiary: Pubkey,
+    },
+    MigrateBus {
+        signer: Pubkey,
+        bus: Pubkey,
+    },
+    TransferProof {
+        signer: Pubkey,
+        new_authority: Pubkey,
+        proof: Pubkey,
+        new_proof: Pubkey,
+    },
+    AddMinerKey {
+        signer: Pubkey,
+        proof: Pubkey,
+        miner: Pubkey,
+    },
+    RemoveMinerKey {
+        signer: Pubkey,
+        proof: Pubkey,
+        miner: Pubkey,
+    },
+    MineRelayed {
+        signer: Pubkey,
+        bus: Pubkey,
+        proof: Pubkey,
+        boost: Pubkey,
+        relayer_proof: Pubkey,
+        referrer_proof: Option<Pubkey>,
+        solution: Solution,
+        fee_bps: u64,
+    },
+    ClaimSplit {
+        signer: Pubkey,
+        proof: Pubkey,
+        beneficiaries: Vec<(Pubkey, u16)>,
+        amount: u64,
+    },
+    Initialize {
+        signer: Pubkey,
+    },
+    MigrateConfig {
+        signer: Pubkey,
+    },
+    SetParams {
+        signer: Pubkey,
+        args: SetParams,
+    },
+    ProposeAdmin {
+        signer: Pubkey,
+        new_admin: Pubkey,
+        timelock: i64,
+    },
+    AcceptAdmin {
+        signer: Pubkey,
+    },
+    AddBus {
+        signer: Pubkey,
+        bus: Pubkey,
+    },
+    SetGuardian {
+        signer: Pubkey,
+        guardian: Pubkey,
+    },
+    SetPaused {
+        signer: Pubkey,
+        paused: u64,
+    },
+}
+
+/// Decode a GARY instruction from its program id, data, and account keys.
+pub fn decode_instruction(
+    program_id: &Pubkey,
+    data: &[u8],
+    accounts: &[Pubkey],
+) -> Result<Decoded, DecodeError> {
+    if program_id.ne(&crate::ID) {
+        return Err(DecodeError::InvalidProgramId);
+    }
+    let (tag, data) = data.split_first().ok_or(DecodeError::InvalidData)?;
+    let ix = GaryInstruction::try_from(*tag).or(Err(DecodeError::InvalidData))?;
+    let decoded = match ix {
+        GaryInstruction::Claim => {
+            let args = Claim::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, beneficiary, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Claim {
+                signer,
+                beneficiary,
+                proof,
+                amount: u64::from_le_bytes(args.amount),
+            }
+        }
+        GaryInstruction::Close => {
+            let [signer, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Close { signer, proof }
+        }
+        GaryInstruction::Mine => {
+            let args = Mine::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, bus, _config, proof, _, _, boost, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Mine {
+                signer,
+                bus,
+                proof,
+                boost,
+                referrer_proof: accounts.get(9).copied(),
+                solution: Solution::new(args.digest, args.nonce),
+            }
+        }
+        GaryInstruction::Open => {
+            let [signer, miner, payer, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Open {
+                signer,
+                miner,
+                payer,
+                proof,
+                referrer_proof: accounts.get(6).copied(),
+            }
+        }
+        GaryInstruction::Reset => {
+            if accounts.len() < 10 {
+                return Err(DecodeError::NotEnoughAccounts);
+            }
+            Decoded::Reset {
+                signer: accounts[0],
+                busses: accounts[10..].to_vec(),
+            }
+        }
+        GaryInstruction::Update => {
+            let [signer, miner, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Update {
+                signer,
+                miner,
+                proof,
+            }
+        }
+        GaryInstruction::MigrateProof => {
+            let [signer, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::MigrateProof { signer, proof }
+        }
+        GaryInstruction::SetClaimDelegate => {
+            let args = SetClaimDelegate::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::SetClaimDelegate {
+                signer,
+                proof,
+                delegate: args.delegate,
+                beneficiary: args.beneficiary,
+            }
+        }
+        GaryInstruction::MigrateBus => {
+            let [signer, bus, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::MigrateBus { signer, bus }
+        }
+        GaryInstruction::TransferProof => {
+            let [signer, new_authority, proof, new_proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::TransferProof {
+                signer,
+                new_authority,
+                proof,
+                new_proof,
+            }
+        }
+        GaryInstruction::AddMinerKey => {
+            let args = AddMinerKey::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::AddMinerKey {
+                signer,
+                proof,
+                miner: args.miner,
+            }
+        }
+        GaryInstruction::RemoveMinerKey => {
+            let args = RemoveMinerKey::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::RemoveMinerKey {
+                signer,
+                proof,
+                miner: args.miner,
+            }
+        }
+        GaryInstruction::MineRelayed => {
+            let args = MineRelayed::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, bus, _config, proof, _, _, boost, _boost_proof, _boost_config, relayer_proof, ..] =
+                *accounts
+            else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::MineRelayed {
+                signer,
+                bus,
+                proof,
+                boost,
+                relayer_proof,
+                referrer_proof: accounts.get(10).copied(),
+                solution: Solution::new(args.digest, args.nonce),
+                fee_bps: u64::from_le_bytes(args.fee_bps),
+            }
+        }
+        GaryInstruction::ClaimSplit => {
+            let args = ClaimSplit::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, proof, _treasury, _treasury_tokens, _token_program, ref beneficiaries @ ..] =
+                *accounts
+            else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::ClaimSplit {
+                signer,
+                proof,
+                beneficiaries: beneficiaries
+                    .iter()
+                    .zip(args.weights)
+                    .map(|(beneficiary, weight)| (*beneficiary, u16::from_le_bytes(weight)))
+                    .collect(),
+                amount: u64::from_le_bytes(args.amount),
+            }
+        }
+        GaryInstruction::Initialize => {
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Initialize { signer }
+        }
+        GaryInstruction::MigrateConfig => {
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::MigrateConfig { signer }
+        }
+        GaryInstruction::SetParams => {
+            let args = SetParams::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::SetParams {
+                signer,
+                args: *args,
+            }
+        }
+        GaryInstruction::ProposeAdmin => {
+            let args = ProposeAdmin::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::ProposeAdmin {
+                signer,
+                new_admin: args.new_admin,
+                timelock: i64::from_le_bytes(args.timelock),
+            }
+        }
+        GaryInstruction::AcceptAdmin => {
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::AcceptAdmin { signer }
+        }
+        GaryInstruction::AddBus => {
+            let [signer, bus, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::AddBus { signer, bus }
+        }
+        GaryInstruction::SetGuardian => {
+            let args = SetGuardian::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::SetGuardian {
+                signer,
+                guardian: args.guardian,
+            }
+        }
+        GaryInstruction::SetPaused => {
+            let args = SetPaused::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::SetPaused {
+                signer,
+                paused: u64::from_le_bytes(args.paused),
+            }
+        }
+    };
+    Ok(decoded)
+}
+
+/// DecodedEvent is an event logged by the GARY program.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecodedEvent {
+    Mine(MineEvent),
+    Claim(ClaimEvent),
+    Close(CloseEvent),
+    Open(OpenEvent),
+    Relay(RelayEvent),
+    Referral(ReferralEvent),
+    Reset(ResetEvent),
+    Update(UpdateEvent),
+    TransferProof(TransferProofEvent),
+}
+
+/// Decode every event the GARY program logged in a transaction, in log order.
+///
+/// Events logged with `sol_log_data` are read from `Program data:` lines, and mine events are
+/// read from the return data the program sets. Events of programs which invoke GARY are skipped.
+pub fn decode_events(logs: &[String]) -> Vec<DecodedEvent> {
+    program_log_data(logs, &crate::ID)
+        .into_iter()
+        .filter_map(|data| match data {
+            LogData::Data(data) => decode_event(&data),
+            LogData::Return(setter, data) if setter.eq(&crate::ID) => {
+                read_event(&data).map(DecodedEvent::Mine)
+            }
+            LogData::Return(..) => None,
+        })
+        .collect()
+}
+
+fn decode_event(data: &[u8]) -> Option<DecodedEvent> {
+    let event = match GaryEvent::from_log_data(data)? {
+        GaryEvent::Claim => DecodedEvent::Claim(read_event(data)?),
+        GaryEvent::Close => DecodedEvent::Close(read_event(data)?),
+        GaryEvent::Open => DecodedEvent::Open(read_event(data)?),
+        GaryEvent::Reset => DecodedEvent::Reset(read_event(data)?),
+        GaryEvent::Update => DecodedEvent::Update(read_event(data)?),
+        GaryEvent::TransferProof => DecodedEvent::TransferProof(read_event(data)?),
+        GaryEvent::Relay => DecodedEvent::Relay(read_event(data)?),
+        GaryEvent::Referral => DecodedEvent::Referral(read_event(data)?),
+    };
+    Some(event)
+}
+
+/// Read a Pod event from logged data, which need not be aligned.
+pub fn read_event<T: Pod>(data: &[u8]) -> Option<T> {
+    bytemuck::try_pod_read_unaligned(data).ok()
+}
+
+/// LogData is a payload a program emitted through its transaction logs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogData {
+    /// Data logged with `sol_log_data`.
+    Data(Vec<u8>),
+
+    /// Return data the program returned with, and the program which set it.
+    Return(Pubkey, Vec<u8>),
+}
+
+/// Collect the payloads a program emitted in a transaction's logs, in log order.
+///
+/// The runtime logs the program invocation stack, so data is only attributed to the program
+/// which was executing when it was logged. Return data is logged each time a program returns
+/// while it is set, so a program which returns the data of a CPI is attributed a copy of it.
+pub fn program_log_data(logs: &[String], program_id: &Pubkey) -> Vec<LogData> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = vec![];
+    let mut payloads = vec![];
+    for log in logs {
+        if let Some(rest) = log.strip_prefix("Program data: ") {
+            if stack.last() == Some(&program_id.as_str()) {
+                // Each slice passed to sol_log_data is a separate base64 string.
+                let data = rest
+                    .split(' ')
+                    .map(|s| BASE64_STANDARD.decode(s))
+                    .collect::<Result<Vec<_>, _>>();
+                if let Ok(data) = data {
+                    payloads.push(LogData::Data(data.concat()));
+                }
+            }
+        } else if let Some(rest) = log.strip_prefix("Program return: ") {
+            if stack.last() == Some(&program_id.as_str()) {
+                let Some((setter, data)) = rest.split_once(' ') else {
+                    continue;
+                };
+                if let (Ok(setter), Ok(data)) =
+                    (Pubkey::from_str(setter), BASE64_STANDARD.decode(data))
+                {
+                    payloads.push(LogData::Return(setter, data));
+                }
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            let (Some(id), Some(status)) = (words.next(), words.next()) else {
+                continue;
+            };
+            match status {
+                "invoke" => stack.push(id),
+                "success" | "failed" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    payloads
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state::Config;
+
+    use super::*;
+
+    #[test]
+    fn test_decode_mine_instruction() {
+        let signer = Pubkey::new_unique();
+        let bus = crate::consts::BUS_ADDRESSES[3];
+        let boost = Pubkey::new_unique();
+        let solution = Solution::new([7; 32], [9; 8]);
+        let ix = crate::sdk::mine(
+            signer,
+            signer,
+            bus,
+            solution,
+            boost,
+            Pubkey::new_unique(),
+            None,
+        );
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+        let Decoded::Mine {
+            signer: s,
+            bus: b,
+            proof,
+            boost: bst,
+            referrer_proof,
+            solution: sol,
+        } = decode_instruction(&ix.program_id, &ix.data, &keys).unwrap()
+        else {
+            panic!("expected a mine instruction");
+        };
+        assert_eq!(s, signer);
+        assert_eq!(b, bus);
+        assert_eq!(proof, crate::state::proof_pda(signer).0);
+        assert_eq!(bst, boost);
+        assert_eq!(referrer_proof, None);
+        assert_eq!(sol.d, solution.d);
+        assert_eq!(sol.n, solution.n);
+
+        // Truncated data, missing accounts, and foreign programs are rejected.
+        assert_eq!(
+            decode_instruction(&ix.program_id, &ix.data[..10], &keys).unwrap_err(),
+            DecodeError::InvalidData
+        );
+        assert_eq!(
+            decode_instruction(&ix.program_id, &ix.data, &keys[..6]).unwrap_err(),
+            DecodeError::NotEnoughAccounts
+        );
+        assert_eq!(
+            decode_instruction(&Pubkey::new_unique(), &ix.data, &keys).unwrap_err(),
+            DecodeError::InvalidProgramId
+        );
+        assert_eq!(
+            decode_instruction(&ix.program_id, &[250], &keys).unwrap_err(),
+            DecodeError::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_decode_mine_relayed_instruction() {
+        let signer = Pubkey::new_unique();
+        let relayer = Pubkey::new_unique();
+        let referrer_proof = Pubkey::new_unique();
+        let solution = Solution::new([7; 32], [9; 8]);
+        let ix = crate::sdk::mine_relayed(
+            signer,
+            signer,
+            crate::consts::BUS_ADDRESSES[0],
+            solution,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            relayer,
+            250,
+            Some(referrer_proof),
+        );
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+        let Decoded::MineRelayed {
+            proof,
+            relayer_proof,
+            referrer_proof: referrer,
+            fee_bps,
+            ..
+        } = decode_instruction(&ix.program_id, &ix.data, &keys).unwrap()
+        else {
+            panic!("expected a mine relayed instruction");
+        };
+        assert_eq!(proof, crate::state::proof_pda(signer).0);
+        assert_eq!(relayer_proof, crate::state::proof_pda(relayer).0);
+        assert_eq!(referrer, Some(referrer_proof));
+        assert_eq!(fee_bps, 250);
+        assert_eq!(
+            decode_instruction(&ix.program_id, &ix.data, &keys[..9]).unwrap_err(),
+            DecodeError::NotEnoughAccounts
+        );
+    }
+
+    #[test]
+    fn test_decode_claim_split_instruction() {
+        let signer = Pubkey::new_unique();
+        let beneficiaries = vec![
+            (Pubkey::new_unique(), 7_000),
+            (Pubkey::new_unique(), 3_000),
+        ];
+        let ix = crate::sdk::claim_split(signer, &beneficiaries, 1_000);
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+        let Decoded::ClaimSplit {
+            signer: s,
+            proof,
+            beneficiaries: b,
+            amount,
+        } = decode_instruction(&ix.program_id, &ix.data, &keys).unwrap()
+        else {
+            panic!("expected a claim split instruction");
+        };
+        assert_eq!(s, signer);
+        assert_eq!(proof, crate::state::proof_pda(signer).0);
+        assert_eq!(b, beneficiaries);
+        assert_eq!(amount, 1_000);
+    }
+
+    #[test]
+    fn test_decode_reset_instruction() {
+        let signer = Pubkey::new_unique();
+        let config = Config {
+            bus_count: 5,
+            ..Zeroable::zeroed()
+        };
+        let ix = crate::sdk::reset(signer, &config);
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|a| a.pubkey).collect();
+        let Decoded::Reset { signer: s, busses } =
+            decode_instruction(&ix.program_id, &ix.data, &keys).unwrap()
+        else {
+            panic!("expected a reset instruction");
+        };
+        assert_eq!(s, signer);
+        assert_eq!(busses, crate::consts::BUS_ADDRESSES[..5].to_vec());
+    }
+
+    #[test]
+    fn test_decode_events() {
+        let gary = crate::ID.to_string();
+        let other = Pubkey::new_unique().to_string();
+        let claim = ClaimEvent {
+            disc: GaryEvent::Claim as u64,
+            amount: 100,
+            ..Zeroable::zeroed()
+        };
+        let mine = MineEvent {
+            difficulty: 12,
+            net_reward: 5,
+            ..Zeroable::zeroed()
+        };
+        let foreign = CloseEvent {
+            disc: GaryEvent::Close as u64,
+            ..Zeroable::zeroed()
+        };
+        let mine_data = BASE64_STANDARD.encode(mine.to_bytes());
+        let logs = vec![
+            format!("Program {} invoke [1]", other),
+            format!(
+                "Program data: {}",
+                BASE64_STANDARD.encode(foreign.to_bytes())
+            ),
+            format!("Program {} invoke [2]", gary),
+            format!("Program data: {}", BASE64_STANDARD.encode(claim.to_bytes())),
+            format!("Program return: {} {}", gary, mine_data),
+            format!("Program {} consumed 1000 of 200000 compute units", gary),
+            format!("Program {} success", gary),
+            format!("Program return: {} {}", gary, mine_data),
+            format!("Program {} success", other),
+        ];
+        assert_eq!(
+            decode_events(&logs),
+            vec![DecodedEvent::Claim(claim), DecodedEvent::Mine(mine)]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use crate::consts::*;
+
+/// The number of minutes in a year, the length of one emissions period at the initial rate.
+pub const MINUTES_PER_YEAR: u64 = 525_600;
+
+/// The number of halvings in the emissions schedule.
+pub const EMISSIONS_HALVINGS: usize = 40;
+
+/// A period of the emissions schedule, during which the target emissions rate is constant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmissionsPeriod {
+    /// The supply at which this period ends.
+    pub supply_threshold: u64,
+
+    /// The target emissions rate in GARY/min while the supply is below the threshold.
+    pub rate: u64,
+}
+
+/// The emissions schedule. The rate halves with every period, and each period lasts roughly
+/// half as long as the one before it, starting with one year at `TARGET_MINUTE_REWARDS`.
+pub const EMISSIONS_SCHEDULE: [EmissionsPeriod; EMISSIONS_HALVINGS] = emissions_schedule();
+
+/// The target emissions rate once the supply exceeds every period of the schedule.
+pub const TAIL_EMISSIONS_RATE: u64 = TARGET_MINUTE_REWARDS >> EMISSIONS_HALVINGS;
+
+const fn emissions_schedule() -> [EmissionsPeriod; EMISSIONS_HALVINGS] {
+    let mut schedule = [EmissionsPeriod {
+        supply_threshold: 0,
+        rate: 0,
+    }; EMISSIONS_HALVINGS];
+    let mut threshold_minutes = MINUTES_PER_YEAR;
+    let mut i = 0;
+    while i < EMISSIONS_HALVINGS {
+        schedule[i] = EmissionsPeriod {
+            supply_threshold: threshold_minutes * TARGET_MINUTE_REWARDS,
+            rate: TARGET_MINUTE_REWARDS >> i,
+        };
+        threshold_minutes += MINUTES_PER_YEAR >> (i + 1);
+        i += 1;
+    }
+    schedule
+}
+
+/// Returns the target emissions rate (GARY / min) at the given supply.
+///
+/// Emissions stop entirely once the supply reaches `MAX_SUPPLY`.
+pub fn emissions_at_supply(supply: u64) -> u64 {
+    if supply >= MAX_SUPPLY {
+        return 0;
+    }
+    EMISSIONS_SCHEDULE
+        .iter()
+        .find(|period| supply < period.supply_threshold)
+        .map_or(TAIL_EMISSIONS_RATE, |period| period.rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The floating point schedule this table replaces.
+    fn legacy_emissions_at_supply(current_supply: u64) -> u64 {
+        if current_supply >= MAX_SUPPLY {
+            return 0;
+        }
+        let mut rate = 1f64;
+        let mut supply_threshold = 525_600;
+        for _ in 0..40 {
+            if current_supply < supply_threshold * TARGET_MINUTE_REWARDS {
+                return (rate * TARGET_MINUTE_REWARDS as f64) as u64;
+            }
+            rate *= 0.5;
+            supply_threshold += (525_600f64 * rate) as u64;
+        }
+        (rate * TARGET_MINUTE_REWARDS as f64) as u64
+    }
+
+    #[test]
+    fn test_schedule_is_monotonic() {
+        let mut prev = EMISSIONS_SCHEDULE[0];
+        for period in EMISSIONS_SCHEDULE.iter().skip(1) {
+            assert!(period.supply_threshold >= prev.supply_threshold);
+            assert!(period.rate <= prev.rate);
+            prev = *period;
+        }
+        assert!(prev.supply_threshold < MAX_SUPPLY);
+    }
+
+    #[test]
+    fn test_matches_legacy_schedule() {
+        for period in EMISSIONS_SCHEDULE.iter() {
+            for supply in [
+                period.supply_threshold.saturating_sub(1),
+                period.supply_threshold,
+                period.supply_threshold + 1,
+            ] {
+                assert_eq!(emissions_at_supply(supply), legacy_emissions_at_supply(supply));
+            }
+        }
+        for supply in (0..MAX_SUPPLY).step_by((MAX_SUPPLY / 100_000) as usize) {
+            assert_eq!(emissions_at_supply(supply), legacy_emissions_at_supply(supply));
+        }
+        assert_eq!(emissions_at_supply(MAX_SUPPLY), 0);
+        assert_eq!(emissions_at_supply(u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_never_exceeds_max_supply() {
+        // Every period, emitting at the largest possible epoch mint from just below its
+        // threshold, stays within the max supply.
+        let max_epoch_minutes = MAX_EPOCH_MINUTES as u64 * MAX_BUS_COUNT;
+        for period in EMISSIONS_SCHEDULE.iter() {
+            let supply = period.supply_threshold - 1;
+            let rate = emissions_at_supply(supply);
+            assert!(supply + rate * max_epoch_minutes <= MAX_SUPPLY);
+        }
+
+        // Minting the schedule epoch by epoch, at the largest possible epoch mint, never
+        // crosses the max supply.
+        for epoch_minutes in [1, INITIAL_EPOCH_MINUTES as u64, MAX_EPOCH_MINUTES as u64] {
+            for bus_count in [1, BUS_COUNT as u64, MAX_BUS_COUNT] {
+                let mut supply = 0u64;
+                loop {
+                    let rate = emissions_at_supply(supply);
+                    if rate == 0 {
+                        break;
+                    }
+                    supply += rate * epoch_minutes * bus_count;
+                    assert!(supply <= MAX_SUPPLY);
+                }
+            }
+        }
+    }
+}
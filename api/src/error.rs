@@ -19,6 +19,18 @@ pub enum GaryError {
     MaxSupply = 6,
     #[error("The proof does not match the expected account")]
     AuthFailed = 7,
+    #[error("The provided parameters are out of bounds")]
+    InvalidParams = 8,
+    #[error("The admin transfer is still timelocked")]
+    AdminTimelocked = 9,
+    #[error("The proof account must be migrated to the versioned layout")]
+    ProofNotMigrated = 10,
+    #[error("The proof already has the maximum number of miner keys")]
+    TooManyMinerKeys = 11,
+    #[error("The relayer fee cannot be greater than the maximum relayer fee")]
+    RelayerFeeTooHigh = 12,
+    #[error("The instruction is paused")]
+    Paused = 13,
 }
 
 error!(GaryError);
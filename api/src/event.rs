@@ -1,5 +1,30 @@
 use steel::*;
 
+/// Discriminates the events logged with `sol_log_data`.
+///
+/// Every logged event stores its type in the leading `disc` field, so indexers can tell
+/// them apart before deserializing the rest of the payload.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum GaryEvent {
+    Claim = 0,
+    Close = 1,
+    Open = 2,
+    Reset = 3,
+    Update = 4,
+    TransferProof = 5,
+    Relay = 6,
+    Referral = 7,
+}
+
+impl GaryEvent {
+    /// Read the event type from logged event data.
+    pub fn from_log_data(data: &[u8]) -> Option<Self> {
+        let disc = u64::from_le_bytes(data.get(..8)?.try_into().ok()?);
+        u8::try_from(disc).ok()?.try_into().ok()
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct MineEvent {
@@ -13,4 +38,115 @@ pub struct MineEvent {
     pub net_staker_boost_reward: u64,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ClaimEvent {
+    pub disc: u64,
+    pub authority: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CloseEvent {
+    pub disc: u64,
+    pub authority: Pubkey,
+    pub proof: Pubkey,
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct OpenEvent {
+    pub disc: u64,
+    pub authority: Pubkey,
+    pub miner: Pubkey,
+    pub proof: Pubkey,
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct RelayEvent {
+    pub disc: u64,
+    pub authority: Pubkey,
+    pub relayer: Pubkey,
+    pub amount: u64,
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ReferralEvent {
+    pub disc: u64,
+    pub authority: Pubkey,
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ResetEvent {
+    pub disc: u64,
+    pub base_reward_rate: u64,
+    pub min_difficulty: u64,
+    pub target_emmissions_rate: u64,
+    pub amount_minted: u64,
+    pub reset_reward: u64,
+    pub taxes: u64,
+    pub fines: u64,
+    pub supply: u64,
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct TransferProofEvent {
+    pub disc: u64,
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub balance: u64,
+    pub ts: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UpdateEvent {
+    pub disc: u64,
+    pub authority: Pubkey,
+    pub miner: Pubkey,
+    pub ts: i64,
+}
+
 event!(MineEvent);
+event!(ClaimEvent);
+event!(CloseEvent);
+event!(OpenEvent);
+event!(RelayEvent);
+event!(ReferralEvent);
+event!(ResetEvent);
+event!(TransferProofEvent);
+event!(UpdateEvent);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_from_log_data() {
+        let event = ResetEvent {
+            disc: GaryEvent::Reset as u64,
+            ..Zeroable::zeroed()
+        };
+        assert_eq!(
+            GaryEvent::from_log_data(event.to_bytes()),
+            Some(GaryEvent::Reset)
+        );
+        assert_eq!(GaryEvent::from_log_data(&[0; 4]), None);
+        assert_eq!(GaryEvent::from_log_data(&[255; 8]), None);
+    }
+}
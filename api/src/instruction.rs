@@ -11,9 +11,24 @@ pub enum GaryInstruction {
     Open = 3,
     Reset = 4,
     Update = 5,
+    MigrateProof = 6,
+    SetClaimDelegate = 7,
+    MigrateBus = 8,
+    TransferProof = 9,
+    AddMinerKey = 10,
+    RemoveMinerKey = 11,
+    MineRelayed = 12,
+    ClaimSplit = 13,
 
     // Admin
     Initialize = 100,
+    MigrateConfig = 101,
+    SetParams = 102,
+    ProposeAdmin = 103,
+    AcceptAdmin = 104,
+    AddBus = 105,
+    SetGuardian = 106,
+    SetPaused = 107,
 }
 
 #[repr(C)]
@@ -45,14 +60,126 @@ pub struct Reset {}
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Update {}
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateProof {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetClaimDelegate {
+    pub delegate: Pubkey,
+    pub beneficiary: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateBus {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TransferProof {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AddMinerKey {
+    pub miner: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct RemoveMinerKey {
+    pub miner: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MineRelayed {
+    pub digest: HashDigest,
+    pub nonce: [u8; 8],
+    pub fee_bps: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimSplit {
+    pub amount: [u8; 8],
+    pub weights: [[u8; 2]; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Initialize {}
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateConfig {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetParams {
+    pub taxes_address: Pubkey,
+    pub fines_address: Pubkey,
+    pub taxes_percent: [u8; 8],
+    pub fines_percent: [u8; 8],
+    pub tolerance: [u8; 8],
+    pub epoch_minutes: [u8; 8],
+    pub smoothing_factor: [u8; 8],
+    pub base_reward_rate_min_threshold: [u8; 8],
+    pub base_reward_rate_max_threshold: [u8; 8],
+    pub reset_reward: [u8; 8],
+    pub recycle_percent: [u8; 8],
+    pub retarget_mode: [u8; 8],
+    pub ema_window: [u8; 8],
+    pub referral_percent: [u8; 8],
+    pub referral_window: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ProposeAdmin {
+    pub new_admin: Pubkey,
+    pub timelock: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AcceptAdmin {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AddBus {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetGuardian {
+    pub guardian: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetPaused {
+    pub paused: [u8; 8],
+}
+
 instruction!(GaryInstruction, Claim);
 instruction!(GaryInstruction, Close);
 instruction!(GaryInstruction, Mine);
 instruction!(GaryInstruction, Open);
 instruction!(GaryInstruction, Reset);
 instruction!(GaryInstruction, Update);
+instruction!(GaryInstruction, MigrateProof);
+instruction!(GaryInstruction, SetClaimDelegate);
+instruction!(GaryInstruction, MigrateBus);
+instruction!(GaryInstruction, TransferProof);
+instruction!(GaryInstruction, AddMinerKey);
+instruction!(GaryInstruction, RemoveMinerKey);
+instruction!(GaryInstruction, MineRelayed);
+instruction!(GaryInstruction, ClaimSplit);
 instruction!(GaryInstruction, Initialize);
+instruction!(GaryInstruction, MigrateConfig);
+instruction!(GaryInstruction, SetParams);
+instruction!(GaryInstruction, ProposeAdmin);
+instruction!(GaryInstruction, AcceptAdmin);
+instruction!(GaryInstruction, AddBus);
+instruction!(GaryInstruction, SetGuardian);
+instruction!(GaryInstruction, SetPaused);
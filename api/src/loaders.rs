@@ -2,22 +2,20 @@ use steel::*;
 
 use crate::{
     consts::*,
-    state::{Config, Treasury},
+    state::{Bus, Config, EpochStats, Proof, ProofExtension, Treasury},
 };
 
 pub trait GaryAccountInfoValidation {
     fn is_bus(&self) -> Result<&Self, ProgramError>;
     fn is_config(&self) -> Result<&Self, ProgramError>;
+    fn is_epoch_stats(&self) -> Result<&Self, ProgramError>;
     fn is_treasury(&self) -> Result<&Self, ProgramError>;
     fn is_treasury_tokens(&self) -> Result<&Self, ProgramError>;
 }
 
 impl GaryAccountInfoValidation for AccountInfo<'_> {
     fn is_bus(&self) -> Result<&Self, ProgramError> {
-        if !BUS_ADDRESSES.contains(self.key) {
-            return Err(ProgramError::InvalidSeeds);
-        }
-        Ok(self)
+        self.is_type::<Bus>(&crate::ID)
     }
 
     fn is_config(&self) -> Result<&Self, ProgramError> {
@@ -25,6 +23,11 @@ impl GaryAccountInfoValidation for AccountInfo<'_> {
             .is_type::<Config>(&crate::ID)
     }
 
+    fn is_epoch_stats(&self) -> Result<&Self, ProgramError> {
+        self.has_address(&EPOCH_STATS_ADDRESS)?
+            .is_type::<EpochStats>(&crate::ID)
+    }
+
     fn is_treasury(&self) -> Result<&Self, ProgramError> {
         self.has_address(&TREASURY_ADDRESS)?
             .is_type::<Treasury>(&crate::ID)
@@ -34,3 +37,57 @@ impl GaryAccountInfoValidation for AccountInfo<'_> {
         self.has_address(&TREASURY_TOKENS_ADDRESS)
     }
 }
+
+/// Proof accounts may have either the legacy or the versioned layout while miners migrate. The
+/// header loaders accept both, and the extension loaders require a migrated account.
+pub trait GaryProofAccountInfo {
+    fn as_proof(&self) -> Result<&Proof, ProgramError>;
+    #[allow(clippy::mut_from_ref)]
+    fn as_proof_mut(&self) -> Result<&mut Proof, ProgramError>;
+    fn as_proof_extension(&self) -> Result<&ProofExtension, ProgramError>;
+    #[allow(clippy::mut_from_ref)]
+    fn as_proof_extension_mut(&self) -> Result<&mut ProofExtension, ProgramError>;
+}
+
+impl GaryProofAccountInfo for AccountInfo<'_> {
+    fn as_proof(&self) -> Result<&Proof, ProgramError> {
+        self.has_owner(&crate::ID)?;
+        let data = self.try_borrow_data()?;
+        unsafe {
+            Proof::try_from_account_data(std::slice::from_raw_parts(data.as_ptr(), data.len()))
+        }
+    }
+
+    fn as_proof_mut(&self) -> Result<&mut Proof, ProgramError> {
+        self.has_owner(&crate::ID)?;
+        let mut data = self.try_borrow_mut_data()?;
+        unsafe {
+            Proof::try_from_account_data_mut(std::slice::from_raw_parts_mut(
+                data.as_mut_ptr(),
+                data.len(),
+            ))
+        }
+    }
+
+    fn as_proof_extension(&self) -> Result<&ProofExtension, ProgramError> {
+        self.has_owner(&crate::ID)?;
+        let data = self.try_borrow_data()?;
+        unsafe {
+            ProofExtension::try_from_account_data(std::slice::from_raw_parts(
+                data.as_ptr(),
+                data.len(),
+            ))
+        }
+    }
+
+    fn as_proof_extension_mut(&self) -> Result<&mut ProofExtension, ProgramError> {
+        self.has_owner(&crate::ID)?;
+        let mut data = self.try_borrow_mut_data()?;
+        unsafe {
+            ProofExtension::try_from_account_data_mut(std::slice::from_raw_parts_mut(
+                data.as_mut_ptr(),
+                data.len(),
+            ))
+        }
+    }
+}
@@ -1,10 +1,17 @@
 use gary_drillx::Solution;
+#[cfg(not(target_os = "solana"))]
+use solana_program::{
+    address_lookup_table::AddressLookupTableAccount,
+    hash::Hash,
+    message::{v0, CompileError, VersionedMessage},
+};
+use solana_program::{address_lookup_table, clock::Slot};
 use steel::*;
 
 use crate::{
     consts::*,
     instruction::*,
-    state::{bus_pda, config_pda, proof_pda, treasury_pda},
+    state::{bus_pda, config_pda, proof_pda, treasury_pda, Config},
 };
 
 /// Builds an auth instruction.
@@ -36,6 +43,62 @@ pub fn claim(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instruction {
     }
 }
 
+/// Builds a claim instruction signed by the proof's claim delegate.
+pub fn claim_as_delegate(
+    signer: Pubkey,
+    authority: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+) -> Instruction {
+    let proof = proof_pda(authority).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new(TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: Claim {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds a claim split instruction, which pays `amount` out to up to
+/// [`MAX_CLAIM_SPLIT_BENEFICIARIES`] beneficiaries by their basis point weights.
+pub fn claim_split(signer: Pubkey, beneficiaries: &[(Pubkey, u16)], amount: u64) -> Instruction {
+    let proof = proof_pda(signer).0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(proof, false),
+        AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+        AccountMeta::new(TREASURY_TOKENS_ADDRESS, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+    ];
+    accounts.extend(
+        beneficiaries
+            .iter()
+            .map(|(beneficiary, _)| AccountMeta::new(*beneficiary, false)),
+    );
+    let mut weights = [[0; 2]; MAX_CLAIM_SPLIT_BENEFICIARIES];
+    for (weight, (_, bps)) in weights.iter_mut().zip(beneficiaries) {
+        *weight = bps.to_le_bytes();
+    }
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: ClaimSplit {
+            amount: amount.to_le_bytes(),
+            weights,
+        }
+        .to_bytes(),
+    }
+}
+
 /// Builds a close instruction.
 pub fn close(signer: Pubkey) -> Instruction {
     let proof = proof_pda(signer).0;
@@ -44,13 +107,16 @@ pub fn close(signer: Pubkey) -> Instruction {
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(proof, false),
-            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
         ],
         data: Close {}.to_bytes(),
     }
 }
 
 /// Builds a mine instruction.
+///
+/// Proofs opened with a referrer must pass the `referrer_proof` of their extension while the
+/// referral window is open, so the referrer is paid its share of taxes.
 pub fn mine(
     signer: Pubkey,
     authority: Pubkey,
@@ -58,23 +124,21 @@ pub fn mine(
     solution: Solution,
     boost: Pubkey,
     boost_config: Pubkey,
+    referrer_proof: Option<Pubkey>,
 ) -> Instruction {
     let proof = proof_pda(authority).0;
-    let taxes_proof = proof_pda(TAXES_ADDRESSES).0;
-    let fines_proof = proof_pda(FINES_ADDRESSES).0;
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(signer, true),
         AccountMeta::new(bus, false),
         AccountMeta::new_readonly(CONFIG_ADDRESS, false),
         AccountMeta::new(proof, false),
-        AccountMeta::new(taxes_proof, false),
-        AccountMeta::new(fines_proof, false),
         AccountMeta::new_readonly(sysvar::instructions::ID, false),
         AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
         AccountMeta::new_readonly(boost, false),
         AccountMeta::new(proof_pda(boost).0, false),
         AccountMeta::new_readonly(boost_config, false),
     ];
+    accounts.extend(referrer_proof.map(|referrer_proof| AccountMeta::new(referrer_proof, false)));
     Instruction {
         program_id: crate::ID,
         accounts,
@@ -86,58 +150,243 @@ pub fn mine(
     }
 }
 
+/// Builds the instructions to mine a solution: the proof auth, a reset if `reset_config` is
+/// given, and the mine instruction itself.
+///
+/// Miners using the boost program should call `gary_boost_api::sdk::mine_instructions` instead,
+/// which appends the rotate instruction. Compile the result with [`v0_message`].
+#[allow(clippy::too_many_arguments)]
+pub fn mine_instructions(
+    signer: Pubkey,
+    authority: Pubkey,
+    bus: Pubkey,
+    solution: Solution,
+    boost: Pubkey,
+    boost_config: Pubkey,
+    referrer_proof: Option<Pubkey>,
+    reset_config: Option<&Config>,
+) -> Vec<Instruction> {
+    let mut ixs = vec![auth(proof_pda(authority).0)];
+    if let Some(config) = reset_config {
+        ixs.push(reset(signer, config));
+    }
+    ixs.push(mine(
+        signer,
+        authority,
+        bus,
+        solution,
+        boost,
+        boost_config,
+        referrer_proof,
+    ));
+    ixs
+}
+
+/// Builds a mine relayed instruction, which pays `fee_bps` of the miner reward to the proof of
+/// the relayer paying the transaction fees.
+#[allow(clippy::too_many_arguments)]
+pub fn mine_relayed(
+    signer: Pubkey,
+    authority: Pubkey,
+    bus: Pubkey,
+    solution: Solution,
+    boost: Pubkey,
+    boost_config: Pubkey,
+    relayer: Pubkey,
+    fee_bps: u64,
+    referrer_proof: Option<Pubkey>,
+) -> Instruction {
+    let proof = proof_pda(authority).0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(bus, false),
+        AccountMeta::new_readonly(CONFIG_ADDRESS, false),
+        AccountMeta::new(proof, false),
+        AccountMeta::new_readonly(sysvar::instructions::ID, false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+        AccountMeta::new_readonly(boost, false),
+        AccountMeta::new(proof_pda(boost).0, false),
+        AccountMeta::new_readonly(boost_config, false),
+        AccountMeta::new(proof_pda(relayer).0, false),
+    ];
+    accounts.extend(referrer_proof.map(|referrer_proof| AccountMeta::new(referrer_proof, false)));
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: MineRelayed {
+            digest: solution.d,
+            nonce: solution.n,
+            fee_bps: fee_bps.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
 /// Builds an open instruction.
-pub fn open(signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instruction {
+///
+/// The `referrer_proof` is paid a share of the taxes on the new proof's hashes for a limited
+/// window after the open.
+pub fn open(
+    signer: Pubkey,
+    miner: Pubkey,
+    payer: Pubkey,
+    referrer_proof: Option<Pubkey>,
+) -> Instruction {
     let proof_pda = proof_pda(signer);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(miner, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new(proof_pda.0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+    ];
+    accounts.extend(
+        referrer_proof.map(|referrer_proof| AccountMeta::new_readonly(referrer_proof, false)),
+    );
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: Open {}.to_bytes(),
+    }
+}
+
+/// Derives the addresses of the first `bus_count` bus accounts.
+pub fn bus_addresses(bus_count: u64) -> Vec<Pubkey> {
+    (0..bus_count)
+        .map(|id| match BUS_ADDRESSES.get(id as usize) {
+            Some(address) => *address,
+            None => bus_pda(id as u8).0,
+        })
+        .collect()
+}
+
+/// Builds a reset instruction.
+///
+/// The signer's proof is credited the crank reward, if it exists. The taxes and fines accrued on
+/// the busses are swept into the proofs of the configured fee addresses.
+pub fn reset(signer: Pubkey, config: &Config) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(CONFIG_ADDRESS, false),
+        AccountMeta::new(EPOCH_STATS_ADDRESS, false),
+        AccountMeta::new(MINT_ADDRESS, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(TREASURY_TOKENS_ADDRESS, false),
+        AccountMeta::new_readonly(spl_token::ID, false),
+        AccountMeta::new(proof_pda(signer).0, false),
+        AccountMeta::new(proof_pda(config.taxes_address).0, false),
+        AccountMeta::new(proof_pda(config.fines_address).0, false),
+    ];
+    accounts.extend(
+        bus_addresses(config.bus_count)
+            .into_iter()
+            .map(|address| AccountMeta::new(address, false)),
+    );
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: Reset {}.to_bytes(),
+    }
+}
+
+// Build an update instruction.
+pub fn update(signer: Pubkey, miner: Pubkey) -> Instruction {
+    let proof = proof_pda(signer).0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new_readonly(miner, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new(proof_pda.0, false),
+            AccountMeta::new(proof, false),
+        ],
+        data: Update {}.to_bytes(),
+    }
+}
+
+/// Builds a transfer proof instruction. Both the current and the new authority must sign.
+pub fn transfer_proof(signer: Pubkey, new_authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(new_authority, true),
+            AccountMeta::new(proof_pda(signer).0, false),
+            AccountMeta::new(proof_pda(new_authority).0, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
         ],
-        data: Open {}.to_bytes(),
+        data: TransferProof {}.to_bytes(),
     }
 }
 
-/// Builds a reset instruction.
-pub fn reset(signer: Pubkey) -> Instruction {
+/// Builds a migrate proof instruction.
+pub fn migrate_proof(signer: Pubkey, authority: Pubkey) -> Instruction {
+    let proof = proof_pda(authority).0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(BUS_ADDRESSES[0], false),
-            AccountMeta::new(BUS_ADDRESSES[1], false),
-            AccountMeta::new(BUS_ADDRESSES[2], false),
-            AccountMeta::new(BUS_ADDRESSES[3], false),
-            AccountMeta::new(BUS_ADDRESSES[4], false),
-            AccountMeta::new(BUS_ADDRESSES[5], false),
-            AccountMeta::new(BUS_ADDRESSES[6], false),
-            AccountMeta::new(BUS_ADDRESSES[7], false),
-            AccountMeta::new(CONFIG_ADDRESS, false),
-            AccountMeta::new(MINT_ADDRESS, false),
-            AccountMeta::new(TREASURY_ADDRESS, false),
-            AccountMeta::new(TREASURY_TOKENS_ADDRESS, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(system_program::ID, false),
         ],
-        data: Reset {}.to_bytes(),
+        data: MigrateProof {}.to_bytes(),
     }
 }
 
-// Build an update instruction.
-pub fn update(signer: Pubkey, miner: Pubkey) -> Instruction {
+/// Builds a set claim delegate instruction. Pass the default pubkey as the delegate to clear it.
+pub fn set_claim_delegate(signer: Pubkey, delegate: Pubkey, beneficiary: Pubkey) -> Instruction {
     let proof = proof_pda(signer).0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new_readonly(miner, false),
             AccountMeta::new(proof, false),
         ],
-        data: Update {}.to_bytes(),
+        data: SetClaimDelegate {
+            delegate,
+            beneficiary,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds an add miner key instruction.
+pub fn add_miner_key(signer: Pubkey, miner: Pubkey) -> Instruction {
+    let proof = proof_pda(signer).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(proof, false),
+        ],
+        data: AddMinerKey { miner }.to_bytes(),
+    }
+}
+
+/// Builds a remove miner key instruction.
+pub fn remove_miner_key(signer: Pubkey, miner: Pubkey) -> Instruction {
+    let proof = proof_pda(signer).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(proof, false),
+        ],
+        data: RemoveMinerKey { miner }.to_bytes(),
+    }
+}
+
+/// Builds a migrate bus instruction.
+pub fn migrate_bus(signer: Pubkey, bus_id: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(bus_pda(bus_id as u8).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: MigrateBus {}.to_bytes(),
     }
 }
 
@@ -177,6 +426,7 @@ pub fn initialize(signer: Pubkey) -> Instruction {
             AccountMeta::new(bus_pdas[6].0, false),
             AccountMeta::new(bus_pdas[7].0, false),
             AccountMeta::new(config_pda.0, false),
+            AccountMeta::new(EPOCH_STATS_ADDRESS, false),
             AccountMeta::new(metadata_pda.0, false),
             AccountMeta::new(mint_pda.0, false),
             AccountMeta::new(treasury_pda.0, false),
@@ -190,3 +440,270 @@ pub fn initialize(signer: Pubkey) -> Instruction {
         data: Initialize {}.to_bytes(),
     }
 }
+
+/// Builds a migrate config instruction.
+pub fn migrate_config(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+            AccountMeta::new(EPOCH_STATS_ADDRESS, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: MigrateConfig {}.to_bytes(),
+    }
+}
+
+/// Builds a set params instruction.
+pub fn set_params(signer: Pubkey, params: SetParams) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(proof_pda(params.taxes_address).0, false),
+            AccountMeta::new_readonly(proof_pda(params.fines_address).0, false),
+        ],
+        data: params.to_bytes(),
+    }
+}
+
+/// Builds an add bus instruction.
+pub fn add_bus(signer: Pubkey, bus_id: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(bus_pda(bus_id as u8).0, false),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: AddBus {}.to_bytes(),
+    }
+}
+
+/// Builds a propose admin instruction.
+pub fn propose_admin(signer: Pubkey, new_admin: Pubkey, timelock: i64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+        ],
+        data: ProposeAdmin {
+            new_admin,
+            timelock: timelock.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds an accept admin instruction.
+pub fn accept_admin(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+        ],
+        data: AcceptAdmin {}.to_bytes(),
+    }
+}
+
+/// Builds a set guardian instruction.
+pub fn set_guardian(signer: Pubkey, guardian: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+        ],
+        data: SetGuardian { guardian }.to_bytes(),
+    }
+}
+
+/// Builds a set paused instruction.
+///
+/// The guardian may only add [`PAUSE_ALL`] flags to the paused instructions, while the admin may
+/// also clear them.
+pub fn set_paused(signer: Pubkey, paused: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(CONFIG_ADDRESS, false),
+            AccountMeta::new(TREASURY_ADDRESS, false),
+        ],
+        data: SetPaused {
+            paused: paused.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Returns the static addresses used by mine and reset transactions, to be stored in a lookup
+/// table.
+pub fn lookup_table_addresses(bus_count: u64) -> Vec<Pubkey> {
+    let mut addresses = vec![
+        CONFIG_ADDRESS,
+        EPOCH_STATS_ADDRESS,
+        MINT_ADDRESS,
+        TREASURY_ADDRESS,
+        TREASURY_TOKENS_ADDRESS,
+        spl_token::ID,
+        system_program::ID,
+        sysvar::instructions::ID,
+        sysvar::slot_hashes::ID,
+    ];
+    addresses.extend(bus_addresses(bus_count));
+    addresses
+}
+
+/// Builds a create lookup table instruction, and returns it with the address of the new table.
+pub fn create_lookup_table(
+    authority: Pubkey,
+    payer: Pubkey,
+    recent_slot: Slot,
+) -> (Instruction, Pubkey) {
+    address_lookup_table::instruction::create_lookup_table(authority, payer, recent_slot)
+}
+
+/// Builds the extend lookup table instructions to add the given addresses to a lookup table.
+///
+/// The addresses are split over several instructions, which should each be sent in their own
+/// transaction.
+pub fn extend_lookup_table(
+    lookup_table: Pubkey,
+    authority: Pubkey,
+    payer: Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    addresses
+        .chunks(LOOKUP_TABLE_EXTEND_LEN)
+        .map(|chunk| {
+            address_lookup_table::instruction::extend_lookup_table(
+                lookup_table,
+                authority,
+                Some(payer),
+                chunk.to_vec(),
+            )
+        })
+        .collect()
+}
+
+/// Compiles instructions into a v0 message, loading every address found in the lookup tables
+/// from them instead of the message itself.
+#[cfg(not(target_os = "solana"))]
+pub fn v0_message(
+    payer: Pubkey,
+    ixs: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedMessage, CompileError> {
+    Ok(VersionedMessage::V0(v0::Message::try_compile(
+        &payer,
+        ixs,
+        lookup_tables,
+        recent_blockhash,
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use gary_drillx::Solution;
+    use solana_program::{
+        address_lookup_table::AddressLookupTableAccount, hash::Hash, message::Message,
+    };
+    use steel::*;
+
+    use crate::{consts::*, state::Config};
+
+    use super::{
+        extend_lookup_table, lookup_table_addresses, mine_instructions, reset, v0_message,
+    };
+
+    #[test]
+    fn test_extend_lookup_table_chunks() {
+        let addresses = lookup_table_addresses(MAX_BUS_COUNT);
+        let ixs = extend_lookup_table(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &addresses,
+        );
+        assert_eq!(ixs.len(), addresses.len().div_ceil(LOOKUP_TABLE_EXTEND_LEN));
+    }
+
+    #[test]
+    fn test_v0_mine_message() {
+        let signer = Pubkey::new_unique();
+        let config = Config {
+            bus_count: BUS_COUNT as u64,
+            taxes_address: Pubkey::new_unique(),
+            fines_address: Pubkey::new_unique(),
+            ..Zeroable::zeroed()
+        };
+        let boost_config = Pubkey::new_unique();
+        let ixs = mine_instructions(
+            signer,
+            signer,
+            BUS_ADDRESSES[0],
+            Solution::new(Default::default(), [0; 8]),
+            Pubkey::new_unique(),
+            boost_config,
+            None,
+            Some(&config),
+        );
+        let mut addresses = lookup_table_addresses(config.bus_count);
+        addresses.push(boost_config);
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses,
+        };
+
+        // Only the signer, the invoked programs, the proofs, and the boost accounts are stored in
+        // the message itself.
+        let message = v0_message(signer, &ixs, &[lookup_table], Hash::default()).unwrap();
+        let legacy_message = Message::new(&ixs, Some(&signer));
+        assert_eq!(message.static_account_keys().len(), 8);
+        assert!(message.static_account_keys().len() < legacy_message.account_keys.len());
+        assert!(message.serialize().len() < legacy_message.serialize().len());
+    }
+
+    #[test]
+    fn test_legacy_reset_transaction_size() {
+        let signer = Pubkey::new_unique();
+        let mut config = Config {
+            taxes_address: Pubkey::new_unique(),
+            fines_address: Pubkey::new_unique(),
+            ..Zeroable::zeroed()
+        };
+        let transaction_size =
+            |ixs: &[Instruction]| 1 + 64 + Message::new(ixs, Some(&signer)).serialize().len();
+
+        // The room taken by the compute budget, boost rotate and tip instructions.
+        let reserved_size = 170;
+
+        // A reset of every bus fits on its own.
+        config.bus_count = MAX_BUS_COUNT;
+        assert!(
+            transaction_size(&[reset(signer, &config)]) + reserved_size <= MAX_TRANSACTION_SIZE
+        );
+
+        // Up to the bundled limit, it also fits alongside a mine.
+        config.bus_count = MAX_BUNDLED_RESET_BUS_COUNT;
+        let ixs = mine_instructions(
+            signer,
+            signer,
+            BUS_ADDRESSES[0],
+            Solution::new(Default::default(), [0; 8]),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Some(Pubkey::new_unique()),
+            Some(&config),
+        );
+        assert!(transaction_size(&ixs) + reserved_size <= MAX_TRANSACTION_SIZE);
+    }
+}
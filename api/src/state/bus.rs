@@ -1,9 +1,14 @@
+use std::mem::size_of;
+
 use steel::*;
 
+use crate::consts::DIFFICULTY_HISTOGRAM_LEN;
+
 use super::GaryAccount;
 
-/// Bus accounts are responsible for distributing mining rewards. There are 8 busses total
-/// to minimize write-lock contention and allow Solana to process mine instructions in parallel.
+/// Bus accounts are responsible for distributing mining rewards. There are `Config::bus_count`
+/// busses total to minimize write-lock contention and allow Solana to process mine instructions
+/// in parallel.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct Bus {
@@ -20,6 +25,40 @@ pub struct Bus {
     /// The largest known stake balance seen by the bus this epoch.
     #[deprecated(since = "2.8.0", note = "Top balance is no longer tracked or used")]
     pub top_balance: u64,
+
+    /// The number of hashes accepted by the bus this epoch.
+    pub hashes: u64,
+
+    /// The rewards this bus has paid out in the current epoch.
+    pub paid_rewards: u64,
+
+    /// The number of hashes accepted by the bus this epoch, by difficulty.
+    pub difficulty_histogram: [u64; DIFFICULTY_HISTOGRAM_LEN],
+
+    /// The taxes accrued by this bus, swept into the taxes proof at the next reset.
+    pub taxes: u64,
+
+    /// The fines accrued by this bus, swept into the fines proof at the next reset.
+    pub fines: u64,
+
+    /// Reserved space for future fields.
+    pub _buffer: [u8; 112],
+}
+
+impl Bus {
+    /// The length of bus accounts created before epoch stats were tracked.
+    pub const LEGACY_LEN: usize = 8 + 32;
+
+    /// The length of bus accounts with the current layout.
+    pub const LEN: usize = 8 + size_of::<Bus>();
+
+    /// Records an accepted hash in the epoch counters.
+    pub fn record_hash(&mut self, difficulty: u32, paid_reward: u64) {
+        let bucket = (difficulty as usize).min(DIFFICULTY_HISTOGRAM_LEN - 1);
+        self.difficulty_histogram[bucket] += 1;
+        self.hashes += 1;
+        self.paid_rewards += paid_reward;
+    }
 }
 
 account!(GaryAccount, Bus);
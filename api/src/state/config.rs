@@ -1,5 +1,7 @@
 use steel::*;
 
+use crate::consts::*;
+
 use super::GaryAccount;
 
 /// Config is a singleton account which manages program global variables.
@@ -17,6 +19,234 @@ pub struct Config {
 
     /// The target emissions rate in GARY/min.
     pub target_emmissions_rate: u64,
+
+    /// The address receiving the taxes fee.
+    pub taxes_address: Pubkey,
+
+    /// The address receiving the fines fee.
+    pub fines_address: Pubkey,
+
+    /// The taxes fee, out of FEE_PERCENT_DENOMINATOR.
+    pub taxes_percent: u64,
+
+    /// The fines fee, out of FEE_PERCENT_DENOMINATOR.
+    pub fines_percent: u64,
+
+    /// The spam/liveness tolerance in seconds.
+    pub tolerance: i64,
+
+    /// The number of minutes in a program epoch.
+    pub epoch_minutes: i64,
+
+    /// The factor by which the reward rate may change from one epoch to the next.
+    pub smoothing_factor: u64,
+
+    /// The base reward rate at which the min difficulty is increased.
+    pub base_reward_rate_min_threshold: u64,
+
+    /// The base reward rate at which the min difficulty is decreased.
+    pub base_reward_rate_max_threshold: u64,
+
+    /// The admin authority of the program.
+    pub admin: Pubkey,
+
+    /// The proposed admin authority, awaiting acceptance.
+    pub pending_admin: Pubkey,
+
+    /// The timestamp after which the pending admin may accept.
+    pub pending_admin_at: i64,
+
+    /// The crank reward credited to the proof of the reset signer, per epoch.
+    pub reset_reward: u64,
+
+    /// The number of bus accounts.
+    pub bus_count: u64,
+
+    /// The share of taxes and fines recycled into the reward pool once emissions have ended, out
+    /// of FEE_PERCENT_DENOMINATOR.
+    pub recycle_percent: u64,
+
+    /// The unallocated treasury tokens used to fund the busses once emissions have ended.
+    pub reward_pool: u64,
+
+    /// The algorithm which retargets the base reward rate and min difficulty at each reset, as a
+    /// RetargetMode.
+    pub retarget_mode: u64,
+
+    /// The number of epochs averaged by the EMA retarget mode.
+    pub ema_window: u64,
+
+    /// The moving average of the work done per epoch, tracked by the EMA retarget mode.
+    pub ema_epoch_work: u64,
+
+    /// The share of taxes paid to the referrer of a proof, out of FEE_PERCENT_DENOMINATOR.
+    pub referral_percent: u64,
+
+    /// The number of seconds after a proof is opened during which its referrer is paid.
+    pub referral_window: i64,
+
+    /// The authority allowed to pause instructions, but not to unpause them.
+    pub guardian: Pubkey,
+
+    /// The paused instructions, as a bitmask of the PAUSE flags.
+    pub paused: u64,
+
+    /// The layout version of the config account, counting the migrations applied to it.
+    pub version: u64,
+
+    /// Reserved space for future parameters.
+    pub _buffer: [u8; 832],
+}
+
+/// RetargetMode is the algorithm which updates the base reward rate and min difficulty at reset.
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum RetargetMode {
+    /// Scale the base reward rate by the last epoch, and step the min difficulty by one when the
+    /// rate crosses a threshold.
+    Threshold = 0,
+
+    /// Pay out the target over a moving average of the work per epoch, so the base reward rate
+    /// acts as a fractional difficulty.
+    Ema = 1,
+}
+
+impl Config {
+    /// The duration of a program epoch, in seconds.
+    pub fn epoch_duration(&self) -> i64 {
+        ONE_MINUTE.saturating_mul(self.epoch_minutes)
+    }
+
+    /// The retarget mode, which falls back to thresholds for unknown values.
+    pub fn retarget_mode(&self) -> RetargetMode {
+        RetargetMode::try_from(self.retarget_mode).unwrap_or(RetargetMode::Threshold)
+    }
+
+    /// Returns true if any of the given PAUSE flags are set.
+    pub fn is_paused(&self, flags: u64) -> bool {
+        self.paused & flags != 0
+    }
+
+    /// Sets the tunable parameters to their initial values.
+    pub fn init_params(&mut self) {
+        self.taxes_address = INITIAL_TAXES_ADDRESS;
+        self.fines_address = INITIAL_FINES_ADDRESS;
+        self.taxes_percent = INITIAL_TAXES_PERCENT;
+        self.fines_percent = INITIAL_FINES_PERCENT;
+        self.tolerance = INITIAL_TOLERANCE;
+        self.epoch_minutes = INITIAL_EPOCH_MINUTES;
+        self.smoothing_factor = INITIAL_SMOOTHING_FACTOR;
+        self.base_reward_rate_min_threshold = INITIAL_BASE_REWARD_RATE_MIN_THRESHOLD;
+        self.base_reward_rate_max_threshold = INITIAL_BASE_REWARD_RATE_MAX_THRESHOLD;
+        self.reset_reward = INITIAL_RESET_REWARD;
+        self.recycle_percent = INITIAL_RECYCLE_PERCENT;
+        self.retarget_mode = RetargetMode::Threshold.into();
+        self.ema_window = INITIAL_EMA_WINDOW;
+        self.referral_percent = INITIAL_REFERRAL_PERCENT;
+        self.referral_window = INITIAL_REFERRAL_WINDOW;
+    }
+
+    /// Sets the fields added by each layout version the config is missing to their initial
+    /// values, and bumps it to the current version. Parameters from earlier versions are kept.
+    pub fn migrate(&mut self, admin: Pubkey) {
+        // Economic parameters.
+        if self.version < 1 {
+            self.taxes_address = INITIAL_TAXES_ADDRESS;
+            self.fines_address = INITIAL_FINES_ADDRESS;
+            self.taxes_percent = INITIAL_TAXES_PERCENT;
+            self.fines_percent = INITIAL_FINES_PERCENT;
+            self.tolerance = INITIAL_TOLERANCE;
+            self.epoch_minutes = INITIAL_EPOCH_MINUTES;
+            self.smoothing_factor = INITIAL_SMOOTHING_FACTOR;
+            self.base_reward_rate_min_threshold = INITIAL_BASE_REWARD_RATE_MIN_THRESHOLD;
+            self.base_reward_rate_max_threshold = INITIAL_BASE_REWARD_RATE_MAX_THRESHOLD;
+        }
+
+        // Admin authority.
+        if self.version < 2 {
+            self.admin = admin;
+        }
+
+        // Crank reward.
+        if self.version < 3 {
+            self.reset_reward = INITIAL_RESET_REWARD;
+        }
+
+        // Bus count.
+        if self.version < 4 {
+            self.bus_count = BUS_COUNT as u64;
+        }
+
+        // Reward pool.
+        if self.version < 5 {
+            self.recycle_percent = INITIAL_RECYCLE_PERCENT;
+        }
+
+        // Retarget mode.
+        if self.version < 6 {
+            self.retarget_mode = RetargetMode::Threshold.into();
+            self.ema_window = INITIAL_EMA_WINDOW;
+        }
+
+        // Referrals.
+        if self.version < 7 {
+            self.referral_percent = INITIAL_REFERRAL_PERCENT;
+            self.referral_window = INITIAL_REFERRAL_WINDOW;
+        }
+
+        // Version 8 added the guardian and pause flags, which start unset.
+        self.version = CONFIG_VERSION;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use steel::*;
+
+    use crate::{
+        consts::{BUS_COUNT, CONFIG_VERSION, INITIAL_REFERRAL_PERCENT, INITIAL_RESET_REWARD},
+        state::Config,
+    };
+
+    #[test]
+    fn test_config_migrate_legacy() {
+        let admin = Pubkey::new_unique();
+        let mut config = Config::zeroed();
+        config.migrate(admin);
+
+        let mut expected = Config {
+            admin,
+            bus_count: BUS_COUNT as u64,
+            version: CONFIG_VERSION,
+            ..Zeroable::zeroed()
+        };
+        expected.init_params();
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn test_config_migrate_keeps_earlier_versions() {
+        let admin = Pubkey::new_unique();
+        let mut config = Config::zeroed();
+        config.migrate(admin);
+        config.version = 2;
+        config.taxes_percent = 1;
+        config.reset_reward = 0;
+        config.referral_percent = 0;
+        config.migrate(Pubkey::new_unique());
+
+        // Fields from versions 1 and 2 are kept, while later ones are filled in.
+        assert_eq!(config.taxes_percent, 1);
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.reset_reward, INITIAL_RESET_REWARD);
+        assert_eq!(config.referral_percent, INITIAL_REFERRAL_PERCENT);
+        assert_eq!(config.version, CONFIG_VERSION);
+
+        // A config at the current version is left unchanged.
+        let current = config;
+        config.migrate(Pubkey::new_unique());
+        assert_eq!(config, current);
+    }
 }
 
 account!(GaryAccount, Config);
@@ -0,0 +1,105 @@
+use steel::*;
+
+use crate::consts::{DIFFICULTY_HISTOGRAM_LEN, EPOCH_STATS_LEN};
+
+use super::GaryAccount;
+
+/// EpochStats is a singleton account which keeps a summary of the most recent epochs in a ring
+/// buffer, so network history can be read directly from chain.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct EpochStats {
+    /// The total number of epochs recorded.
+    pub count: u64,
+
+    /// The most recent epoch summaries, indexed by epoch count modulo EPOCH_STATS_LEN.
+    pub epochs: [EpochSummary; EPOCH_STATS_LEN],
+}
+
+/// EpochSummary records the network activity of a single epoch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct EpochSummary {
+    /// The timestamp of the reset which ended the epoch.
+    pub ended_at: i64,
+
+    /// The number of hashes accepted across all busses.
+    pub hashes: u64,
+
+    /// The rewards which would have been paid out if there were no bus limits.
+    pub theoretical_rewards: u64,
+
+    /// The rewards actually paid out by the busses.
+    pub paid_rewards: u64,
+
+    /// The minimum accepted difficulty during the epoch.
+    pub min_difficulty: u64,
+
+    /// The base reward rate during the epoch.
+    pub base_reward_rate: u64,
+
+    /// The amount minted by the reset which ended the epoch.
+    pub amount_minted: u64,
+
+    /// The number of hashes accepted across all busses, by difficulty.
+    pub difficulty_histogram: [u64; DIFFICULTY_HISTOGRAM_LEN],
+}
+
+impl EpochStats {
+    /// Records a new epoch, overwriting the oldest one once the buffer is full.
+    pub fn push(&mut self, summary: EpochSummary) {
+        self.epochs[self.count as usize % EPOCH_STATS_LEN] = summary;
+        self.count += 1;
+    }
+
+    /// The most recently recorded epoch.
+    pub fn latest(&self) -> Option<&EpochSummary> {
+        let i = self.count.checked_sub(1)?;
+        Some(&self.epochs[i as usize % EPOCH_STATS_LEN])
+    }
+
+    /// The most recently recorded epoch, mutably.
+    pub fn latest_mut(&mut self) -> Option<&mut EpochSummary> {
+        let i = self.count.checked_sub(1)?;
+        Some(&mut self.epochs[i as usize % EPOCH_STATS_LEN])
+    }
+
+    /// The recorded epochs, from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &EpochSummary> {
+        let len = (self.count as usize).min(EPOCH_STATS_LEN);
+        let start = self.count as usize - len;
+        (start..self.count as usize).map(|i| &self.epochs[i % EPOCH_STATS_LEN])
+    }
+}
+
+account!(GaryAccount, EpochStats);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(hashes: u64) -> EpochSummary {
+        EpochSummary {
+            hashes,
+            ..Zeroable::zeroed()
+        }
+    }
+
+    #[test]
+    fn test_epoch_stats_empty() {
+        let stats = EpochStats::zeroed();
+        assert!(stats.latest().is_none());
+        assert_eq!(stats.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_epoch_stats_wraps() {
+        let mut stats = EpochStats::zeroed();
+        for i in 0..(EPOCH_STATS_LEN as u64 + 3) {
+            stats.push(summary(i));
+        }
+        assert_eq!(stats.latest().unwrap().hashes, EPOCH_STATS_LEN as u64 + 2);
+        let hashes: Vec<u64> = stats.iter().map(|e| e.hashes).collect();
+        assert_eq!(hashes, (3..EPOCH_STATS_LEN as u64 + 3).collect::<Vec<_>>());
+    }
+}
@@ -1,10 +1,12 @@
 mod bus;
 mod config;
+mod epoch_stats;
 mod proof;
 mod treasury;
 
 pub use bus::*;
 pub use config::*;
+pub use epoch_stats::*;
 pub use proof::*;
 pub use treasury::*;
 
@@ -19,6 +21,7 @@ pub enum GaryAccount {
     Config = 101,
     Proof = 102,
     Treasury = 103,
+    EpochStats = 104,
 }
 
 /// Fetch the PDA of a bus account.
@@ -31,6 +34,11 @@ pub fn config_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[CONFIG], &crate::id())
 }
 
+/// Derive the PDA of the epoch stats account.
+pub fn epoch_stats_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EPOCH_STATS], &crate::id())
+}
+
 /// Derive the PDA of a proof account.
 pub fn proof_pda(authority: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[PROOF, authority.as_ref()], &crate::id())
@@ -1,9 +1,19 @@
+use std::mem::size_of;
+
 use steel::*;
 
+use crate::{
+    consts::{MAX_MINER_KEYS, PROOF_VERSION},
+    error::GaryError,
+};
+
 use super::GaryAccount;
 
 /// Proof accounts track a miner's current hash, claimable rewards, and lifetime stats.
 /// Every miner is allowed one proof account which is required by the program to mine or claim rewards.
+///
+/// The proof fields are a fixed header shared by every layout. Versioned proof accounts append a
+/// [`ProofExtension`] after the header, while legacy accounts opened before versioning do not.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct Proof {
@@ -35,4 +45,230 @@ pub struct Proof {
     pub total_rewards: u64,
 }
 
+/// ProofExtension is the versioned region of a proof account, holding fields added after launch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ProofExtension {
+    /// The layout version of the proof account.
+    pub version: u64,
+
+    /// The signer allowed to claim on behalf of the authority, or the default pubkey if unset.
+    pub claim_delegate: Pubkey,
+
+    /// The only token account the claim delegate may claim to.
+    pub claim_beneficiary: Pubkey,
+
+    /// Additional keypairs with permission to submit hashes, or the default pubkey in unused slots.
+    pub miner_keys: [Pubkey; MAX_MINER_KEYS],
+
+    /// The proof of the miner who referred this one, or the default pubkey if unset.
+    pub referrer: Pubkey,
+
+    /// Timestamp of the open, or zero for proofs migrated from the legacy layout.
+    pub opened_at: i64,
+
+    /// Reserved space for future fields.
+    pub _buffer: [u8; 664],
+}
+
+impl Proof {
+    /// The data length of a legacy proof account.
+    pub const LEGACY_LEN: usize = 8 + size_of::<Proof>();
+
+    /// The data length of a versioned proof account.
+    pub const LEN: usize = Self::LEGACY_LEN + size_of::<ProofExtension>();
+
+    /// Parses the proof header from account data of either layout.
+    pub fn try_from_account_data(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.len() != Self::LEGACY_LEN && data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self::try_header_from_bytes(data)?.0)
+    }
+
+    /// Parses the mutable proof header from account data of either layout.
+    pub fn try_from_account_data_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() != Self::LEGACY_LEN && data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if Self::discriminator().ne(&data[0]) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self::try_header_from_bytes_mut(data)?.0)
+    }
+}
+
+impl ProofExtension {
+    /// Returns true if the key is one of the additional miner keys.
+    pub fn is_miner_key(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.miner_keys.contains(key)
+    }
+
+    /// Adds a miner key to the first unused slot. Adding a key which is already listed is a no-op.
+    pub fn add_miner_key(&mut self, key: Pubkey) -> Result<(), GaryError> {
+        if key == Pubkey::default() {
+            return Err(GaryError::InvalidParams);
+        }
+        if self.is_miner_key(&key) {
+            return Ok(());
+        }
+        let slot = self
+            .miner_keys
+            .iter_mut()
+            .find(|k| **k == Pubkey::default())
+            .ok_or(GaryError::TooManyMinerKeys)?;
+        *slot = key;
+        Ok(())
+    }
+
+    /// Removes a miner key. Removing a key which is not listed is a no-op.
+    pub fn remove_miner_key(&mut self, key: &Pubkey) {
+        for k in self.miner_keys.iter_mut().filter(|k| **k == *key) {
+            *k = Pubkey::default();
+        }
+    }
+
+    /// Returns true if the referrer is paid for hashes submitted at time `t`, given the referral
+    /// window of the config.
+    pub fn is_referral_active(&self, t: i64, referral_window: i64) -> bool {
+        self.referrer != Pubkey::default() && t < self.opened_at.saturating_add(referral_window)
+    }
+
+    /// Initializes the extension of a versioned proof account at the current version.
+    pub fn init_account_data(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() != Proof::LEN {
+            return Err(GaryError::ProofNotMigrated.into());
+        }
+        let extension = bytemuck::try_from_bytes_mut::<Self>(&mut data[Proof::LEGACY_LEN..])
+            .or(Err(ProgramError::InvalidAccountData))?;
+        extension.version = PROOF_VERSION;
+        Ok(extension)
+    }
+
+    /// Parses the extension from the data of a versioned proof account.
+    pub fn try_from_account_data(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.len() != Proof::LEN {
+            return Err(GaryError::ProofNotMigrated.into());
+        }
+        let extension = bytemuck::try_from_bytes::<Self>(Proof::try_header_from_bytes(data)?.1)
+            .or(Err(ProgramError::InvalidAccountData))?;
+        if extension.version != PROOF_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(extension)
+    }
+
+    /// Parses the mutable extension from the data of a versioned proof account.
+    pub fn try_from_account_data_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() != Proof::LEN {
+            return Err(GaryError::ProofNotMigrated.into());
+        }
+        if Proof::discriminator().ne(&data[0]) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let extension =
+            bytemuck::try_from_bytes_mut::<Self>(Proof::try_header_from_bytes_mut(data)?.1)
+                .or(Err(ProgramError::InvalidAccountData))?;
+        if extension.version != PROOF_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(extension)
+    }
+}
+
 account!(GaryAccount, Proof);
+
+#[cfg(test)]
+mod tests {
+    use steel::*;
+
+    use crate::{
+        consts::{MAX_MINER_KEYS, PROOF_VERSION},
+        error::GaryError,
+        state::{Proof, ProofExtension},
+    };
+
+    fn proof_data(len: usize) -> Vec<u8> {
+        let mut data = vec![0u8; len];
+        data[0] = Proof::discriminator();
+        data[8..40].copy_from_slice(&[7; 32]);
+        data
+    }
+
+    #[test]
+    fn test_proof_legacy_layout() {
+        let mut data = proof_data(Proof::LEGACY_LEN);
+        let proof = Proof::try_from_account_data(&data).unwrap();
+        assert_eq!(proof.authority, Pubkey::new_from_array([7; 32]));
+        assert_eq!(
+            ProofExtension::try_from_account_data(&data),
+            Err(GaryError::ProofNotMigrated.into())
+        );
+        assert!(ProofExtension::init_account_data(&mut data).is_err());
+    }
+
+    #[test]
+    fn test_proof_versioned_layout() {
+        let mut data = proof_data(Proof::LEN);
+        assert!(ProofExtension::try_from_account_data(&data).is_err());
+        ProofExtension::init_account_data(&mut data).unwrap();
+        let proof = Proof::try_from_account_data(&data).unwrap();
+        assert_eq!(proof.authority, Pubkey::new_from_array([7; 32]));
+        let extension = ProofExtension::try_from_account_data(&data).unwrap();
+        assert_eq!(extension.version, PROOF_VERSION);
+    }
+
+    #[test]
+    fn test_proof_invalid_layout() {
+        let data = proof_data(Proof::LEGACY_LEN + 1);
+        assert!(Proof::try_from_account_data(&data).is_err());
+        let mut data = proof_data(Proof::LEN);
+        data[0] = 0;
+        assert!(Proof::try_from_account_data(&data).is_err());
+        assert!(Proof::try_from_account_data_mut(&mut data).is_err());
+    }
+
+    #[test]
+    fn test_proof_miner_keys() {
+        let mut extension = ProofExtension::zeroed();
+        assert!(!extension.is_miner_key(&Pubkey::default()));
+        assert_eq!(
+            extension.add_miner_key(Pubkey::default()),
+            Err(GaryError::InvalidParams)
+        );
+
+        // Fill every slot. Re-adding a listed key does not use a slot.
+        let keys: Vec<Pubkey> = (0..MAX_MINER_KEYS).map(|_| Pubkey::new_unique()).collect();
+        for key in keys.iter() {
+            extension.add_miner_key(*key).unwrap();
+            extension.add_miner_key(*key).unwrap();
+        }
+        assert!(keys.iter().all(|k| extension.is_miner_key(k)));
+        assert_eq!(
+            extension.add_miner_key(Pubkey::new_unique()),
+            Err(GaryError::TooManyMinerKeys)
+        );
+
+        // Removing a key frees its slot for reuse.
+        extension.remove_miner_key(&keys[3]);
+        assert!(!extension.is_miner_key(&keys[3]));
+        let key = Pubkey::new_unique();
+        extension.add_miner_key(key).unwrap();
+        assert_eq!(extension.miner_keys[3], key);
+    }
+
+    #[test]
+    fn test_proof_referral() {
+        let mut extension = ProofExtension::zeroed();
+        extension.opened_at = 1_000;
+        assert!(!extension.is_referral_active(1_000, 100));
+
+        // The referrer is paid until the window after the open has passed.
+        extension.referrer = Pubkey::new_unique();
+        assert!(extension.is_referral_active(1_000, 100));
+        assert!(extension.is_referral_active(1_099, 100));
+        assert!(!extension.is_referral_active(1_100, 100));
+        assert!(!extension.is_referral_active(1_000, 0));
+        assert!(extension.is_referral_active(i64::MAX - 1, i64::MAX));
+    }
+}
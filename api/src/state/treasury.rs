@@ -6,6 +6,17 @@ use super::GaryAccount;
 /// the program's global token account.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
-pub struct Treasury {}
+pub struct Treasury {
+    /// A copy of the config's paused instructions, so claims can check them without taking the
+    /// config account.
+    pub paused: u64,
+}
+
+impl Treasury {
+    /// Returns true if any of the given PAUSE flags are set.
+    pub fn is_paused(&self, flags: u64) -> bool {
+        self.paused & flags != 0
+    }
+}
 
 account!(GaryAccount, Treasury);
@@ -0,0 +1,540 @@
+//! Host implementation of the layer 0-3 collision search
+//!
+//! Every function here is a step-for-step port of the matching CUDA kernel in
+//! [`crate::layer_0`] .. [`crate::layer_3`]. One kernel thread handles one pair of
+//! complementary buckets, so the host versions walk those buckets in thread id order and
+//! append each bucket's collisions in the order the kernel would write them into its
+//! per-thread block. Combined with stable sorts that reproduce the merge sort kernels,
+//! the host solver finds the same solutions as the GPU solver.
+
+use crate::hashx::item_hash;
+use crate::params::{EQUIHASH_N_DIV_K, NUM_BUCKETS_BIT};
+use crate::{
+    HashValue, Index, SolutionItem, SolutionItemArray, COLLISIONS_PER_BUCKET,
+    COLLISIONS_PER_THREAD, MAX_ITEMS, MAX_SOLUTIONS, NUM_BUCKETS, NUM_BUCKETS_MASK,
+    REMAINDER_NUM_BUCKETS_MASK,
+};
+use core::cmp::Ordering;
+use hashx::HashX;
+use std::thread;
+use std::vec::Vec;
+
+/// Hash types stored in the collision layers.
+pub trait HostHash: Copy + Eq + Send + Sync {
+    /// The empty slot marker.
+    const ZERO: Self;
+
+    /// Wrapping addition of two partial sums.
+    fn wrapping_add(self, other: Self) -> Self;
+
+    /// Returns true if the lowest `num_bits` bits are all zero.
+    fn low_bits_zero(self, num_bits: usize) -> bool;
+
+    /// Drops the lowest `num_bits` bits.
+    fn shift_out(self, num_bits: usize) -> Self;
+
+    /// The lowest bits of the hash, truncated to usize.
+    fn low_usize(self) -> usize;
+}
+
+macro_rules! impl_host_hash {
+    ($($t:ty),*) => {
+        $(
+            impl HostHash for $t {
+                const ZERO: Self = 0;
+
+                fn wrapping_add(self, other: Self) -> Self {
+                    <$t>::wrapping_add(self, other)
+                }
+
+                fn low_bits_zero(self, num_bits: usize) -> bool {
+                    self & !(<$t>::MAX << num_bits) == 0
+                }
+
+                fn shift_out(self, num_bits: usize) -> Self {
+                    self >> num_bits
+                }
+
+                fn low_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_host_hash!(u64, u128);
+
+/// Collisions found in one layer of the search.
+///
+/// Unlike the device [`crate::mem::LayerData`], which reserves a fixed block per thread and
+/// marks unused slots with a zero hash, collisions are appended back to back. Empty slots
+/// never take part in a collision, so only their positions differ.
+pub struct HostLayer<H: HostHash> {
+    pub hashes: Vec<H>,
+    pub items: Vec<(SolutionItem, SolutionItem)>,
+}
+
+impl<H: HostHash> HostLayer<H> {
+    /// New empty layer.
+    pub const fn new() -> Self {
+        Self {
+            hashes: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Number of collisions in the layer.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns true if the layer holds no collisions.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Removes all collisions, keeping the allocation for the next run.
+    pub fn clear(&mut self) {
+        self.hashes.clear();
+        self.items.clear();
+    }
+
+    fn push(&mut self, h: H, packed_item: (SolutionItem, SolutionItem)) {
+        self.hashes.push(h);
+        self.items.push(packed_item);
+    }
+}
+
+impl<H: HostHash> Default for HostLayer<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub type HostLayer1 = HostLayer<u128>;
+pub type HostLayer2 = HostLayer<u64>;
+
+/// Host version of [`crate::handle_layer0`]. Fills `hashes` with the hash of every item.
+///
+/// The work is split across `threads` workers, each filling a contiguous range.
+pub fn handle_layer0(hashx: &HashX, hashes: &mut Vec<HashValue>, threads: usize) {
+    hashes.resize(MAX_ITEMS as usize, 0);
+    let chunk_size = (MAX_ITEMS as usize).div_ceil(threads.max(1));
+    thread::scope(|s| {
+        for (chunk, out) in hashes.chunks_mut(chunk_size).enumerate() {
+            s.spawn(move || {
+                let first_item = chunk * chunk_size;
+                for (i, h) in out.iter_mut().enumerate() {
+                    *h = item_hash(hashx, (first_item + i) as SolutionItem);
+                }
+            });
+        }
+    });
+}
+
+/// Host version of [`crate::sort_hashes`].
+pub fn sort_hashes(hashes: &[HashValue], indices: &mut Vec<Index>) {
+    reset_indices(indices, hashes.len());
+    indices.sort_by_key(|&i| hash_key(hashes[i as usize]));
+}
+
+/// Host version of [`crate::handle_layer1`].
+pub fn handle_layer1(hashes: &[HashValue], indices: &[Index], layer1: &mut HostLayer1) {
+    layer1.clear();
+    for bucket in 0..=NUM_BUCKETS / 2 {
+        let mut cnt = 0;
+        search_layer(
+            hashes,
+            indices,
+            bucket,
+            EQUIHASH_N_DIV_K,
+            &|h, val| last_buckets_bit(h).cmp(val),
+            &mut |sum, item| {
+                if cnt < COLLISIONS_PER_THREAD {
+                    layer1.push(sum, item);
+                    cnt += 1;
+                }
+            },
+        );
+    }
+}
+
+/// Host version of [`crate::sort_layer1`] and [`crate::sort_layer2`].
+pub fn sort_layer<H: HostHash>(layer: &HostLayer<H>, indices: &mut Vec<Index>) {
+    reset_indices(indices, layer.len());
+    indices.sort_by_key(|&i| layer_hash_key(layer.hashes[i as usize]));
+}
+
+/// Host version of [`crate::handle_layer2`].
+pub fn handle_layer2(prev_layer: &HostLayer1, prev_indices: &[Index], current_layer: &mut HostLayer2) {
+    current_layer.clear();
+    for bucket in 0..=NUM_BUCKETS / 2 {
+        let mut cnt = 0;
+        search_layer(
+            &prev_layer.hashes,
+            prev_indices,
+            bucket,
+            EQUIHASH_N_DIV_K,
+            &cmp_layer_bucket,
+            &mut |sum, loc| {
+                if cnt < COLLISIONS_PER_THREAD {
+                    current_layer.push(sum as u64, loc);
+                    cnt += 1;
+                }
+            },
+        );
+    }
+}
+
+/// Host version of [`crate::handle_layer3`]. Returns at most [`MAX_SOLUTIONS`] unsorted
+/// solutions.
+pub fn handle_layer3(
+    layer1: &HostLayer1,
+    layer2: &HostLayer2,
+    layer2_indices: &[Index],
+) -> Vec<SolutionItemArray> {
+    let mut results = Vec::with_capacity(MAX_SOLUTIONS);
+    for bucket in 0..=NUM_BUCKETS / 2 {
+        search_layer(
+            &layer2.hashes,
+            layer2_indices,
+            bucket,
+            EQUIHASH_N_DIV_K * 2,
+            &cmp_layer_bucket,
+            &mut |_sum, (l2_first_item, l2_second_item)| {
+                if results.len() < MAX_SOLUTIONS {
+                    let (a, b) = layer2.items[l2_first_item as usize];
+                    let (c, d) = layer2.items[l2_second_item as usize];
+                    results.push([
+                        layer1.items[a as usize].0,
+                        layer1.items[a as usize].1,
+                        layer1.items[b as usize].0,
+                        layer1.items[b as usize].1,
+                        layer1.items[c as usize].0,
+                        layer1.items[c as usize].1,
+                        layer1.items[d as usize].0,
+                        layer1.items[d as usize].1,
+                    ]);
+                }
+            },
+        );
+    }
+    results
+}
+
+/// Host version of `collision::search_layer`, for the bucket handled by one kernel thread.
+///
+/// Reads that would land outside of `indices` on the device end the search instead.
+pub(crate) fn search_layer<H: HostHash>(
+    hashes: &[H],
+    indices: &[Index],
+    first_bucket: usize,
+    num_bits: usize,
+    cmp: &impl Fn(H, &usize) -> Ordering,
+    predicate: &mut impl FnMut(H, (SolutionItem, SolutionItem)),
+) {
+    let hash_at = |pos: usize| indices.get(pos).map(|&i| hashes[i as usize]);
+    let mut collide = |first: usize, second: usize| {
+        let (Some(&first_item), Some(&second_item)) = (indices.get(first), indices.get(second))
+        else {
+            return;
+        };
+        let sum = hashes[first_item as usize].wrapping_add(hashes[second_item as usize]);
+        if sum.low_bits_zero(num_bits) {
+            predicate(
+                sum.shift_out(num_bits),
+                (first_item as SolutionItem, second_item as SolutionItem),
+            );
+        }
+    };
+
+    let second_bucket = first_bucket.wrapping_neg() & NUM_BUCKETS_MASK;
+    let start_range_first_bucket =
+        indices.partition_point(|&i| cmp(hashes[i as usize], &first_bucket) == Ordering::Less);
+    let end_range_second_bucket = indices
+        .partition_point(|&i| cmp(hashes[i as usize], &second_bucket) != Ordering::Greater)
+        .wrapping_sub(1);
+    let mut first_iter = start_range_first_bucket;
+    let mut second_iter = end_range_second_bucket;
+
+    // skip 0
+    if first_bucket == 0 {
+        let mut first_iter_0_r = first_iter + 1;
+        while hash_at(first_iter_0_r).is_some_and(|h| in_range(h, 0) && remain_buckets_bit(h) == 0)
+        {
+            first_iter_0_r += 1;
+        }
+        for i in first_iter..first_iter_0_r {
+            for j in i..first_iter_0_r.min(i + 1 + COLLISIONS_PER_BUCKET) {
+                collide(i, j);
+            }
+        }
+        first_iter = first_iter_0_r;
+    }
+
+    let offset = if first_bucket == 0 { 0 } else { 1 };
+    while first_iter < second_iter {
+        let (Some(first_hash), Some(second_hash)) = (hash_at(first_iter), hash_at(second_iter))
+        else {
+            return;
+        };
+        if !in_range(first_hash, first_bucket) || !in_range(second_hash, second_bucket) {
+            return;
+        }
+        let first_remainder = remain_buckets_bit(first_hash);
+        let mut first_r = first_iter + 1;
+        while first_r < second_iter
+            && hash_at(first_r)
+                .is_some_and(|h| in_range(h, first_bucket) && remain_buckets_bit(h) == first_remainder)
+        {
+            first_r += 1;
+        }
+        let first_remainder_complement =
+            (first_remainder + offset).wrapping_neg() & REMAINDER_NUM_BUCKETS_MASK;
+
+        let mut second_remainder = remain_buckets_bit(second_hash);
+        while second_remainder > first_remainder_complement {
+            second_iter = second_iter.wrapping_sub(1);
+            match hash_at(second_iter) {
+                Some(h) if in_range(h, second_bucket) => second_remainder = remain_buckets_bit(h),
+                _ => return,
+            }
+        }
+        while second_remainder == first_remainder_complement {
+            for i in first_iter..first_r.min(first_iter + COLLISIONS_PER_BUCKET) {
+                collide(i, second_iter);
+            }
+            second_iter = second_iter.wrapping_sub(1);
+            match hash_at(second_iter) {
+                Some(h) if in_range(h, second_bucket) => second_remainder = remain_buckets_bit(h),
+                _ => return,
+            }
+        }
+        first_iter = first_r;
+    }
+}
+
+fn reset_indices(indices: &mut Vec<Index>, n: usize) {
+    indices.clear();
+    indices.extend(0..n as Index);
+}
+
+/// Bucket comparison used by layers 2 and 3, where zero hashes mark empty slots.
+fn cmp_layer_bucket<H: HostHash>(h: H, val: &usize) -> Ordering {
+    if h == H::ZERO {
+        Ordering::Less
+    } else {
+        last_buckets_bit(h).cmp(val)
+    }
+}
+
+/// Sort key equivalent to `sorting::cmp_hashes`.
+fn hash_key<H: HostHash>(h: H) -> usize {
+    (last_buckets_bit(h) << (EQUIHASH_N_DIV_K - NUM_BUCKETS_BIT)) | remain_buckets_bit(h)
+}
+
+/// Sort key equivalent to `sorting::cmp_layer_hash`, ordering empty slots first.
+fn layer_hash_key<H: HostHash>(h: H) -> usize {
+    if h == H::ZERO {
+        0
+    } else {
+        (1 << EQUIHASH_N_DIV_K) | hash_key(h)
+    }
+}
+
+fn in_range<H: HostHash>(h: H, bucket: usize) -> bool {
+    last_buckets_bit(h) == bucket
+}
+
+fn last_buckets_bit<H: HostHash>(h: H) -> usize {
+    h.low_usize() & NUM_BUCKETS_MASK
+}
+
+fn remain_buckets_bit<H: HostHash>(h: H) -> usize {
+    (h.shift_out(NUM_BUCKETS_BIT)).low_usize() & REMAINDER_NUM_BUCKETS_MASK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Builds a hash whose low `EQUIHASH_N_DIV_K` bits are `low` and whose upper bits tag
+    /// the item, so collisions are easy to predict.
+    fn hash(item: u64, low: u64) -> u128 {
+        ((item as u128) << 64) | low as u128
+    }
+
+    #[test]
+    fn test_search_layer_matches_brute_force() {
+        // Low bits drawn from a small set, so there are plenty of collisions but never more
+        // than COLLISIONS_PER_BUCKET items sharing the same low bits.
+        let mask = (1u64 << EQUIHASH_N_DIV_K) - 1;
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut lows = Vec::new();
+        for _ in 0..64 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let low = (seed >> 20) & mask;
+            lows.push(low);
+            lows.push(low.wrapping_neg() & mask);
+        }
+        let hashes: Vec<u128> = lows
+            .iter()
+            .enumerate()
+            .map(|(i, &low)| hash(i as u64, low))
+            .collect();
+        let mut indices = Vec::new();
+        sort_hashes(&hashes, &mut indices);
+
+        let mut found = BTreeSet::new();
+        for bucket in 0..=NUM_BUCKETS / 2 {
+            search_layer(
+                &hashes,
+                &indices,
+                bucket,
+                EQUIHASH_N_DIV_K,
+                &|h, val| last_buckets_bit(h).cmp(val),
+                &mut |sum, (a, b)| {
+                    assert_eq!(sum, (hashes[a as usize] + hashes[b as usize]) >> EQUIHASH_N_DIV_K);
+                    found.insert((a.min(b), a.max(b)));
+                },
+            );
+        }
+
+        let mut expected = BTreeSet::new();
+        for a in 0..hashes.len() {
+            for b in a + 1..hashes.len() {
+                if (lows[a] + lows[b]) & mask == 0 {
+                    expected.insert((a as SolutionItem, b as SolutionItem));
+                }
+            }
+        }
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_sort_layer_empty_slots_first() {
+        let layer = HostLayer {
+            hashes: vec![5u64 << NUM_BUCKETS_BIT | 3, 0, 1, 7 << NUM_BUCKETS_BIT | 1, 0],
+            items: vec![(0, 0); 5],
+        };
+        let mut indices = Vec::new();
+        sort_layer(&layer, &mut indices);
+        assert_eq!(indices, vec![1, 4, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_find_planted_solution() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            let mut h = 0u128;
+            for _ in 0..2 {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                h = h << 64 | seed as u128;
+            }
+            h
+        };
+
+        // Random item hashes, far too few to collide by chance.
+        let mut hashes: Vec<HashValue> = (0..4096).map(|_| next()).collect();
+
+        // Plant a solution by working down the tree from the layer 3 collision. Each pair of
+        // partial sums is picked to collide on the next `EQUIHASH_N_DIV_K` bits, and shifts to
+        // the sum it was picked from.
+        let n = EQUIHASH_N_DIV_K;
+        let layer2_mask = (1u128 << (128 - n)) - 1;
+        let w = next() as u64 | 1;
+        let layer2 = [w, ((next() as u64) << (2 * n)).wrapping_sub(w)];
+        let mut layer1 = Vec::new();
+        for w in layer2 {
+            let y = next() & layer2_mask | 1;
+            layer1.push(y);
+            layer1.push(((w as u128) << n).wrapping_sub(y) & layer2_mask);
+        }
+        let items: SolutionItemArray = [3, 4000, 1500, 17, 2222, 999, 3210, 64];
+        for (pair, y) in items.chunks(2).zip(layer1) {
+            let h = next();
+            hashes[pair[0] as usize] = h;
+            hashes[pair[1] as usize] = (y << n).wrapping_sub(h);
+        }
+
+        // The planted items meet the verifier's partial sums at every level of the tree.
+        for (level, chunk) in [(1, 2), (2, 4), (4, 8)] {
+            for branch in items.chunks(chunk) {
+                let sum = branch
+                    .iter()
+                    .fold(0u128, |sum, &item| sum.wrapping_add(hashes[item as usize]));
+                assert_eq!(sum & ((1 << (level * n)) - 1), 0);
+            }
+        }
+
+        let mut indices = Vec::new();
+        let mut layer1 = HostLayer1::new();
+        let mut layer2 = HostLayer2::new();
+        sort_hashes(&hashes, &mut indices);
+        handle_layer1(&hashes, &indices, &mut layer1);
+        sort_layer(&layer1, &mut indices);
+        handle_layer2(&layer1, &indices, &mut layer2);
+        sort_layer(&layer2, &mut indices);
+        let results = handle_layer3(&layer1, &layer2, &indices);
+        assert_eq!(layer1.len(), 4);
+        assert_eq!(layer2.len(), 2);
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            crate::Solution::sort_from_array(results[0]).as_ref(),
+            crate::Solution::sort_from_array(items).as_ref()
+        );
+    }
+
+    /// Known answer for a zero challenge and nonce.
+    ///
+    /// The items were recorded by replaying this search bucket by bucket from disk, since the
+    /// full layers need more than 20GB of memory. The replay matched these functions on
+    /// thousands of seeds at reduced parameters, and both solutions pass the HashX verifier.
+    /// They were not recorded from the CUDA solver. The GPU tests of `gary-drillx` assert the
+    /// same digest, so a CUDA run checks the two solvers agree.
+    #[test]
+    #[ignore = "needs more than 20GB of memory"]
+    fn test_known_answer() {
+        let seed = [0; 40];
+        let hashx = hashx::HashXBuilder::new().build(&seed).unwrap();
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let mut hashes = Vec::new();
+        let mut indices = Vec::new();
+        let mut layer1 = HostLayer1::new();
+        let mut layer2 = HostLayer2::new();
+        handle_layer0(&hashx, &mut hashes, threads);
+        sort_hashes(&hashes, &mut indices);
+        handle_layer1(&hashes, &indices, &mut layer1);
+        sort_layer(&layer1, &mut indices);
+        handle_layer2(&layer1, &indices, &mut layer2);
+        sort_layer(&layer2, &mut indices);
+        let results = handle_layer3(&layer1, &layer2, &indices);
+        assert_eq!(
+            results,
+            vec![
+                [
+                    472301444, 69182004, 417903407, 488917005, 252942584, 313984964, 534079123,
+                    118738395,
+                ],
+                [
+                    222326738, 89703448, 170915421, 99642520, 53478779, 240297584, 150711393,
+                    473848757,
+                ],
+            ]
+        );
+
+        // The first solution in tree order is the drillx digest.
+        let solution = crate::Solution::sort_from_array(results[0]);
+        assert_eq!(
+            solution.to_bytes(),
+            [
+                52, 162, 31, 4, 132, 191, 38, 28, 47, 179, 232, 24, 13, 72, 36, 29, 248, 152, 19,
+                15, 196, 7, 183, 18, 219, 205, 19, 7, 147, 102, 213, 31,
+            ]
+        );
+    }
+}
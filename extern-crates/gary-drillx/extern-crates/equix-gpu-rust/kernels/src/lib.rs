@@ -15,6 +15,8 @@ mod params;
 
 #[cfg(feature = "solve")]
 mod collision;
+#[cfg(all(feature = "solve-cpu", not(feature = "solve")))]
+pub mod cpu;
 mod err;
 mod hashx;
 #[cfg(feature = "solve")]
@@ -0,0 +1,70 @@
+use equix_kernels::cpu::{
+    handle_layer0, handle_layer1, handle_layer2, handle_layer3, sort_hashes, sort_layer,
+    HostLayer1, HostLayer2,
+};
+use equix_kernels::{Error, HashValue, Index, Solution};
+use hashx::HashX;
+use std::thread;
+
+/// Host counterpart of the CUDA `find_solutions`, running the same layer 0-3 search
+/// without a GPU.
+pub(crate) fn find_solutions(func: &HashX, mem: &mut SolverMemory) -> Vec<Solution> {
+    let SolverMemory {
+        hashes,
+        indices,
+        layer1,
+        layer2,
+        threads,
+    } = mem;
+
+    // find all hashes
+    handle_layer0(func, hashes, *threads);
+    sort_hashes(hashes, indices);
+
+    // layer 1
+    handle_layer1(hashes, indices, layer1);
+    sort_layer(layer1, indices);
+
+    // layer 2
+    handle_layer2(layer1, indices, layer2);
+    sort_layer(layer2, indices);
+
+    // layer 3
+    handle_layer3(layer1, layer2, indices)
+        .into_iter()
+        .map(Solution::sort_from_array)
+        .collect()
+}
+
+/// Temporary memory used by the Equi-X solver
+///
+/// Buffers grow on the first solve and are reused by later calls to
+/// [`crate::EquiX::solve_with_memory()`].
+pub struct SolverMemory {
+    hashes: Vec<HashValue>,
+    indices: Vec<Index>,
+    layer1: HostLayer1,
+    layer2: HostLayer2,
+    threads: usize,
+}
+
+impl SolverMemory {
+    /// New empty memory, usable as solver temporary space.
+    ///
+    /// Item hashing is spread over all available cores.
+    pub fn new() -> Result<Self, Error> {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        Ok(Self::with_threads(threads))
+    }
+
+    /// New empty memory, hashing items on `threads` cores.
+    pub fn with_threads(threads: usize) -> Self {
+        Self {
+            hashes: Vec::new(),
+            indices: Vec::new(),
+            layer1: HostLayer1::new(),
+            layer2: HostLayer2::new(),
+            threads: threads.max(1),
+        }
+    }
+}
@@ -7,6 +7,10 @@ pub use solver::solver::*;
 
 #[cfg(feature = "solve")]
 pub use crate::solver::solver::SolverMemory;
+#[cfg(all(feature = "solve-cpu", not(feature = "solve")))]
+pub mod cpu_solver;
+#[cfg(all(feature = "solve-cpu", not(feature = "solve")))]
+pub use cpu_solver::*;
 pub use equix_kernels::{Solution, SolutionByteArray, SolutionItemArray, check_all_tree_sums, Error};
 #[cfg(feature = "solve")]
 use hashx_cuda::{HashX, HashXBuilder};
@@ -43,7 +47,7 @@ impl EquiX {
     ///
     /// Returns a buffer with a variable number of solutions.
     /// Memory for the solver is allocated dynamically and not reused.
-    #[cfg(feature = "solve")]
+    #[cfg(any(feature = "solve", feature = "solve-cpu"))]
     pub fn solve(&self) -> Result<Vec<Solution>, Error> {
         let mut mem = SolverMemory::new()?;
         self.solve_with_memory(&mut mem)
@@ -64,6 +68,17 @@ impl EquiX {
         Ok(find_solutions(&self.hash, mem)
             .map_err(|e| Error::CudaError(e.to_string()))?)
     }
+
+    /// Search for solutions on the CPU, using the provided [`SolverMemory`].
+    ///
+    /// Returns the same solutions as the CUDA solver, without requiring a GPU.
+    #[cfg(all(feature = "solve-cpu", not(feature = "solve")))]
+    pub fn solve_with_memory(
+        &self,
+        mem: &mut SolverMemory,
+    ) -> Result<Vec<Solution>, Error> {
+        Ok(find_solutions(&self.hash, mem))
+    }
 }
 
 /// Builder for creating [`EquiX`] instances with custom settings
@@ -105,7 +120,7 @@ impl EquiXBuilder {
     /// It's normal for this to fail with a [`HashError::ProgramConstraints`]
     /// for a small fraction of challenge values. Those challenges must be
     /// skipped by solvers and rejected by verifiers.
-    #[cfg(feature = "solve")]
+    #[cfg(any(feature = "solve", feature = "solve-cpu"))]
     pub fn solve(&self, challenge: &[u8]) -> Result<Vec<Solution>, Error> {
         self.build(challenge)?.solve()
     }
@@ -163,7 +178,7 @@ impl Default for EquiXBuilder {
 /// It's normal for this to fail with a [`HashError::ProgramConstraints`] for
 /// a small fraction of challenge values. Those challenges must be skipped
 /// by solvers and rejected by verifiers.
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 pub fn solve(challenge: &[u8]) -> Result<Vec<Solution>, Error> {
     EquiX::new(challenge)?.solve()
 }
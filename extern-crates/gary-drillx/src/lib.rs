@@ -1,14 +1,14 @@
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 pub use equix_gpu_rust as equix;
 
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 use equix_gpu_rust::{SolverMemory, solve, EquiXBuilder};
 use equix_gpu_rust::{verify_bytes, Solution as EquixSolution};
 use sha3::{Digest, Keccak256};
 
 /// Generates a new drillx hash from a challenge and nonce.
 #[inline(always)]
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 pub fn hash(challenge: &[u8; 32], nonce: &[u8; 8]) -> Result<Hash, DrillxError> {
     let digest = digest(challenge, nonce)?;
     Ok(Hash {
@@ -19,7 +19,7 @@ pub fn hash(challenge: &[u8; 32], nonce: &[u8; 8]) -> Result<Hash, DrillxError>
 
 /// Generates a new drillx hash from a challenge and nonce using pre-allocated memory.
 #[inline(always)]
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 pub fn hash_with_memory(
     memory: &mut SolverMemory,
     challenge: &[u8; 32],
@@ -34,7 +34,7 @@ pub fn hash_with_memory(
 
 /// Generates drillx hashes from a challenge and nonce using pre-allocated memory.
 #[inline(always)]
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 pub fn hashes_with_memory(
     memory: &mut SolverMemory,
     challenge: &[u8; 32],
@@ -64,7 +64,7 @@ pub fn seed(challenge: &[u8; 32], nonce: &[u8; 8]) -> [u8; 40] {
 
 /// Constructs a keccak digest from a challenge and nonce using equix hashes.
 #[inline(always)]
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 fn digest(challenge: &[u8; 32], nonce: &[u8; 8]) -> Result<HashDigest, DrillxError> {
     let seed = seed(challenge, nonce);
     let solutions = solve(&seed).map_err(|e| DrillxError::BadEquix(e.to_string()))?;
@@ -78,7 +78,7 @@ fn digest(challenge: &[u8; 32], nonce: &[u8; 8]) -> Result<HashDigest, DrillxErr
 
 /// Constructs a keccak digest from a challenge and nonce using equix hashes and pre-allocated memory.
 #[inline(always)]
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 fn digest_with_memory(
     memory: &mut SolverMemory,
     challenge: &[u8; 32],
@@ -101,7 +101,7 @@ fn digest_with_memory(
 
 /// Constructs a keccak digest from a challenge and nonce using equix hashes and pre-allocated memory.
 #[inline(always)]
-#[cfg(feature = "solve")]
+#[cfg(any(feature = "solve", feature = "solve-cpu"))]
 fn digests_with_memory(
     memory: &mut SolverMemory,
     challenge: &[u8; 32],
@@ -225,12 +225,33 @@ pub enum DrillxError {
     NoSolutions,
 }
 
-#[cfg(feature = "default")]
 #[cfg(test)]
 mod tests {
-    use crate::{hashes_with_memory, Solution};
+    #[cfg(any(feature = "default", feature = "solve-cpu"))]
+    use crate::hash;
+    #[cfg(feature = "default")]
+    use crate::hashes_with_memory;
+    #[cfg(any(feature = "default", feature = "solve-cpu"))]
+    use crate::Solution;
+    #[cfg(feature = "default")]
     use equix_gpu_rust::solver::SolverMemory;
 
+    /// Digest of a zero challenge and nonce, recorded from the host solver. See the
+    /// `equix_kernels::cpu` known answer for how it was recorded.
+    #[cfg(any(feature = "default", feature = "solve-cpu"))]
+    const KNOWN_DIGEST: [u8; 32] = [
+        52, 162, 31, 4, 132, 191, 38, 28, 47, 179, 232, 24, 13, 72, 36, 29, 248, 152, 19, 15, 196,
+        7, 183, 18, 219, 205, 19, 7, 147, 102, 213, 31,
+    ];
+
+    /// Hash of [`KNOWN_DIGEST`].
+    #[cfg(any(feature = "default", feature = "solve-cpu"))]
+    const KNOWN_HASH: [u8; 32] = [
+        151, 179, 83, 70, 69, 131, 196, 19, 146, 157, 60, 16, 157, 210, 228, 100, 25, 78, 89, 159,
+        0, 168, 240, 159, 148, 212, 79, 96, 125, 34, 143, 122,
+    ];
+
+    #[cfg(feature = "default")]
     #[test]
     fn test() {
         let _ctx = cust::quick_init().unwrap();
@@ -241,4 +262,24 @@ mod tests {
         assert!(solution.is_valid(&[0; 32]));
         dbg!(&hx);
     }
+
+    /// The CUDA solver finds the same digest as the host solver.
+    #[cfg(feature = "default")]
+    #[test]
+    fn test_hash_known_answer_gpu() {
+        let _ctx = cust::quick_init().unwrap();
+        let hx = hash(&[0; 32], &[0; 8]).unwrap();
+        assert_eq!(hx.d, KNOWN_DIGEST);
+        assert_eq!(hx.h, KNOWN_HASH);
+    }
+
+    #[cfg(all(feature = "solve-cpu", not(feature = "solve")))]
+    #[test]
+    #[ignore = "needs more than 20GB of memory"]
+    fn test_hash_known_answer() {
+        let hx = hash(&[0; 32], &[0; 8]).unwrap();
+        assert_eq!(hx.d, KNOWN_DIGEST);
+        assert_eq!(hx.h, KNOWN_HASH);
+        assert!(Solution::new(hx.d, [0; 8]).is_valid(&[0; 32]));
+    }
 }
@@ -0,0 +1,243 @@
+pub use gary_api::decode::DecodeError;
+use steel::*;
+
+use crate::instruction::*;
+
+/// Decoded is a boost instruction with its args parsed and its accounts named.
+///
+/// Sysvars, programs, and token accounts derived from the boost are omitted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decoded {
+    Claim {
+        signer: Pubkey,
+        beneficiary: Pubkey,
+        boost: Pubkey,
+        stake: Pubkey,
+        amount: u64,
+    },
+    Close {
+        signer: Pubkey,
+        boost: Pubkey,
+        stake: Pubkey,
+    },
+    Deposit {
+        signer: Pubkey,
+        boost: Pubkey,
+        mint: Pubkey,
+        sender: Pubkey,
+        stake: Pubkey,
+        amount: u64,
+    },
+    Open {
+        signer: Pubkey,
+        payer: Pubkey,
+        boost: Pubkey,
+        mint: Pubkey,
+        stake: Pubkey,
+    },
+    Rotate {
+        signer: Pubkey,
+    },
+    Withdraw {
+        signer: Pubkey,
+        beneficiary: Pubkey,
+        boost: Pubkey,
+        mint: Pubkey,
+        stake: Pubkey,
+        amount: u64,
+    },
+    ClaimToStake {
+        signer: Pubkey,
+        boost: Pubkey,
+        proof: Pubkey,
+        stake: Pubkey,
+        amount: u64,
+    },
+    Activate {
+        signer: Pubkey,
+        boost: Pubkey,
+    },
+    Deactivate {
+        signer: Pubkey,
+        boost: Pubkey,
+    },
+    Initialize {
+        signer: Pubkey,
+    },
+    New {
+        signer: Pubkey,
+        boost: Pubkey,
+        mint: Pubkey,
+        expires_at: i64,
+        bps: u64,
+    },
+    UpdateAdmin {
+        signer: Pubkey,
+        new_admin: Pubkey,
+    },
+    UpdateBoost {
+        signer: Pubkey,
+        boost: Pubkey,
+        expires_at: i64,
+        bps: u64,
+    },
+}
+
+/// Decode a boost instruction from its program id, data, and account keys.
+pub fn decode_instruction(
+    program_id: &Pubkey,
+    data: &[u8],
+    accounts: &[Pubkey],
+) -> Result<Decoded, DecodeError> {
+    if program_id.ne(&crate::ID) {
+        return Err(DecodeError::InvalidProgramId);
+    }
+    let (tag, data) = data.split_first().ok_or(DecodeError::InvalidData)?;
+    let ix = BoostInstruction::try_from(*tag).or(Err(DecodeError::InvalidData))?;
+    let decoded = match ix {
+        BoostInstruction::Claim => {
+            let args = Claim::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, beneficiary, boost, _boost_proof, _boost_rewards, stake, ..] = *accounts
+            else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Claim {
+                signer,
+                beneficiary,
+                boost,
+                stake,
+                amount: u64::from_le_bytes(args.amount),
+            }
+        }
+        BoostInstruction::Close => {
+            let [signer, boost, stake, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Close {
+                signer,
+                boost,
+                stake,
+            }
+        }
+        BoostInstruction::Deposit => {
+            let args = Deposit::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, boost, _boost_deposits, _boost_proof, _boost_rewards, mint, sender, stake, ..] =
+                *accounts
+            else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Deposit {
+                signer,
+                boost,
+                mint,
+                sender,
+                stake,
+                amount: u64::from_le_bytes(args.amount),
+            }
+        }
+        BoostInstruction::Open => {
+            let [signer, payer, boost, mint, stake, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Open {
+                signer,
+                payer,
+                boost,
+                mint,
+                stake,
+            }
+        }
+        BoostInstruction::Rotate => {
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Rotate { signer }
+        }
+        BoostInstruction::Withdraw => {
+            let args = Withdraw::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, beneficiary, boost, _boost_deposits, _boost_proof, _boost_rewards, mint, stake, ..] =
+                *accounts
+            else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Withdraw {
+                signer,
+                beneficiary,
+                boost,
+                mint,
+                stake,
+                amount: u64::from_le_bytes(args.amount),
+            }
+        }
+        BoostInstruction::ClaimToStake => {
+            let args = ClaimToStake::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, boost, _boost_deposits, _boost_proof, _boost_rewards, proof, stake, ..] =
+                *accounts
+            else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::ClaimToStake {
+                signer,
+                boost,
+                proof,
+                stake,
+                amount: u64::from_le_bytes(args.amount),
+            }
+        }
+        BoostInstruction::Activate => {
+            let [signer, boost, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Activate { signer, boost }
+        }
+        BoostInstruction::Deactivate => {
+            let [signer, boost, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Deactivate { signer, boost }
+        }
+        BoostInstruction::Initialize => {
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Initialize { signer }
+        }
+        BoostInstruction::New => {
+            let args = New::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, boost, _boost_deposits, _boost_rewards, _config, mint, ..] = *accounts
+            else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::New {
+                signer,
+                boost,
+                mint,
+                expires_at: i64::from_le_bytes(args.expires_at),
+                bps: u64::from_le_bytes(args.bps),
+            }
+        }
+        BoostInstruction::UpdateAdmin => {
+            let args = UpdateAdmin::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::UpdateAdmin {
+                signer,
+                new_admin: args.new_admin,
+            }
+        }
+        BoostInstruction::UpdateBoost => {
+            let args = UpdateBoost::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, boost, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::UpdateBoost {
+                signer,
+                boost,
+                expires_at: i64::from_le_bytes(args.expires_at),
+                bps: u64::from_le_bytes(args.bps),
+            }
+        }
+    };
+    Ok(decoded)
+}
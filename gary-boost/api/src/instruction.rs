@@ -11,6 +11,7 @@ pub enum BoostInstruction {
     Open = 3,
     Rotate = 4,
     Withdraw = 5,
+    ClaimToStake = 6,
     
     // Admin
     Activate = 100,
@@ -37,6 +38,12 @@ pub struct Claim {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimToStake {
+    pub amount: [u8; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Close {}
@@ -91,6 +98,7 @@ pub struct Withdraw {
 
 instruction!(BoostInstruction, Activate);
 instruction!(BoostInstruction, Claim);
+instruction!(BoostInstruction, ClaimToStake);
 instruction!(BoostInstruction, Close);
 instruction!(BoostInstruction, Deactivate);
 instruction!(BoostInstruction, Deposit);
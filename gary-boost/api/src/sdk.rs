@@ -1,4 +1,5 @@
-use gary_api::state::proof_pda;
+use gary_api::state::{proof_pda, Config};
+use gary_drillx::Solution;
 use steel::*;
 
 use crate::{
@@ -51,6 +52,39 @@ pub fn claim(signer: Pubkey, beneficiary: Pubkey, mint: Pubkey, amount: u64) ->
     }
 }
 
+// Build claim to stake instruction.
+pub fn claim_to_stake(signer: Pubkey, amount: u64) -> Instruction {
+    let mint = gary_api::consts::MINT_ADDRESS;
+    let boost_address = boost_pda(mint).0;
+    let boost_deposits_address =
+        spl_associated_token_account::get_associated_token_address(&boost_address, &mint);
+    let boost_proof_address = proof_pda(boost_address).0;
+    let boost_rewards_address =
+        spl_associated_token_account::get_associated_token_address(&boost_address, &mint);
+    let proof_address = proof_pda(signer).0;
+    let stake_address = stake_pda(signer, boost_address).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(boost_address, false),
+            AccountMeta::new(boost_deposits_address, false),
+            AccountMeta::new(boost_proof_address, false),
+            AccountMeta::new(boost_rewards_address, false),
+            AccountMeta::new(proof_address, false),
+            AccountMeta::new(stake_address, false),
+            AccountMeta::new_readonly(gary_api::consts::TREASURY_ADDRESS, false),
+            AccountMeta::new(gary_api::consts::TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(gary_api::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: ClaimToStake {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
 // Build deactivate instruction.
 pub fn deactivate(signer: Pubkey, mint: Pubkey) -> Instruction {
     let boost_pda = boost_pda(mint);
@@ -182,6 +216,37 @@ pub fn rotate(signer: Pubkey) -> Instruction {
     }
 }
 
+// Build the instructions to mine a solution with the current boost: the proof auth, a reset if
+// `reset_config` is given, the mine instruction, and the rotate instruction.
+pub fn mine_instructions(
+    signer: Pubkey,
+    bus: Pubkey,
+    solution: Solution,
+    boost: Pubkey,
+    referrer_proof: Option<Pubkey>,
+    reset_config: Option<&Config>,
+) -> Vec<Instruction> {
+    let mut ixs = gary_api::sdk::mine_instructions(
+        signer,
+        signer,
+        bus,
+        solution,
+        boost,
+        config_pda().0,
+        referrer_proof,
+        reset_config,
+    );
+    ixs.push(rotate(signer));
+    ixs
+}
+
+// Build the lookup table addresses for mining, including the boost config.
+pub fn lookup_table_addresses(bus_count: u64) -> Vec<Pubkey> {
+    let mut addresses = gary_api::sdk::lookup_table_addresses(bus_count);
+    addresses.push(config_pda().0);
+    addresses
+}
+
 // Build update_boost instruction.
 pub fn update_boost(signer: Pubkey, boost: Pubkey, expires_at: i64, bps: u64) -> Instruction {
     Instruction {
@@ -234,3 +299,42 @@ pub fn withdraw(signer: Pubkey, mint: Pubkey, amount: u64) -> Instruction {
         .to_bytes(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gary_api::{consts::*, sdk::v0_message, state::Config};
+    use gary_drillx::Solution;
+    use solana_program::{address_lookup_table::AddressLookupTableAccount, hash::Hash};
+    use steel::*;
+
+    use super::{lookup_table_addresses, mine_instructions};
+
+    #[test]
+    fn test_v0_mine_transaction_size() {
+        let signer = Pubkey::new_unique();
+        let config = Config {
+            bus_count: MAX_BUS_COUNT,
+            taxes_address: Pubkey::new_unique(),
+            fines_address: Pubkey::new_unique(),
+            ..Zeroable::zeroed()
+        };
+        let ixs = mine_instructions(
+            signer,
+            BUS_ADDRESSES[0],
+            Solution::new(Default::default(), [0; 8]),
+            Pubkey::new_unique(),
+            Some(Pubkey::new_unique()),
+            Some(&config),
+        );
+        assert_eq!(ixs.len(), 4);
+
+        // The auth, reset, mine and rotate instructions fit in one transaction with every bus.
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: lookup_table_addresses(config.bus_count),
+        };
+        let message = v0_message(signer, &ixs, &[lookup_table], Hash::default()).unwrap();
+        let transaction_size = 1 + 64 + message.serialize().len();
+        assert!(transaction_size <= MAX_TRANSACTION_SIZE);
+    }
+}
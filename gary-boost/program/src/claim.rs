@@ -1,4 +1,4 @@
-use gary_api::state::Proof;
+use gary_api::loaders::GaryProofAccountInfo;
 use gary_boost_api::consts::BOOST;
 use gary_boost_api::instruction::Claim;
 use gary_boost_api::state::{Boost, Stake};
@@ -24,7 +24,7 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         .assert(|t| t.mint() == gary_api::consts::MINT_ADDRESS)?;
     let boost = boost_info.as_account_mut::<Boost>(&gary_boost_api::ID)?;
     let boost_proof = boost_proof_info
-        .as_account::<Proof>(&gary_api::ID)?
+        .as_proof()?
         .assert(|p| p.authority == *boost_info.key)?;
     boost_rewards_info
         .is_writable()?
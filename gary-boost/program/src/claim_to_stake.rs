@@ -0,0 +1,84 @@
+use gary_api::loaders::GaryProofAccountInfo;
+use gary_boost_api::prelude::*;
+use steel::*;
+
+/// ClaimToStake claims mining rewards from a proof directly into a stake account of the GARY boost.
+pub fn process_claim_to_stake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = ClaimToStake::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, boost_info, boost_deposits_info, boost_proof_info, boost_rewards_info, proof_info, stake_info, treasury_info, treasury_tokens_info, gary_program, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let boost = boost_info
+        .as_account_mut::<Boost>(&gary_boost_api::ID)?
+        .assert_mut(|b| b.mint == gary_api::consts::MINT_ADDRESS)?;
+    boost_deposits_info
+        .is_writable()?
+        .as_associated_token_account(boost_info.key, &boost.mint)?;
+    let boost_proof = boost_proof_info
+        .as_proof()?
+        .assert(|p| p.authority == *boost_info.key)?;
+    boost_rewards_info
+        .is_writable()?
+        .as_associated_token_account(boost_info.key, &gary_api::consts::MINT_ADDRESS)?;
+    let proof = proof_info
+        .is_writable()?
+        .as_proof()?
+        .assert(|p| p.authority == *signer_info.key)?;
+    let stake = stake_info
+        .as_account_mut::<Stake>(&gary_boost_api::ID)?
+        .assert_mut(|s| s.authority == *signer_info.key)?
+        .assert_mut(|s| s.boost == *boost_info.key)?;
+    gary_program.is_program(&gary_api::ID)?;
+    token_program.is_program(&spl_token::ID)?;
+
+    // Accumulate personal stake rewards.
+    stake.accumulate_rewards(boost, &boost_proof);
+    invoke_signed(
+        &gary_api::sdk::claim(
+            *boost_info.key,
+            *boost_rewards_info.key,
+            boost_proof.balance,
+        ),
+        &[
+            boost_info.clone(),
+            boost_rewards_info.clone(),
+            boost_proof_info.clone(),
+            treasury_info.clone(),
+            treasury_tokens_info.clone(),
+            token_program.clone(),
+            gary_program.clone(),
+        ],
+        &gary_boost_api::ID,
+        &[BOOST, boost.mint.as_ref()],
+    )?;
+
+    // Claim mining rewards from the treasury straight into the boost deposits.
+    let amount = amount.min(proof.balance);
+    solana_program::program::invoke(
+        &gary_api::sdk::claim(*signer_info.key, *boost_deposits_info.key, amount),
+        &[
+            signer_info.clone(),
+            boost_deposits_info.clone(),
+            proof_info.clone(),
+            treasury_info.clone(),
+            treasury_tokens_info.clone(),
+            token_program.clone(),
+            gary_program.clone(),
+        ],
+    )?;
+
+    // Update deposit balances.
+    boost.total_deposits += amount;
+    stake.balance += amount;
+    stake.last_deposit_at = clock.unix_timestamp;
+
+    Ok(())
+}
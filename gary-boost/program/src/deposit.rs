@@ -1,4 +1,4 @@
-use gary_api::state::Proof;
+use gary_api::loaders::GaryProofAccountInfo;
 use gary_boost_api::prelude::*;
 use steel::*;
 
@@ -23,7 +23,7 @@ pub fn process_deposit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResu
         .is_writable()?
         .as_associated_token_account(boost_info.key, &boost.mint)?;
     let boost_proof = boost_proof_info
-        .as_account::<Proof>(&gary_api::ID)?
+        .as_proof()?
         .assert(|p| p.authority == *boost_info.key)?;
     boost_rewards_info
         .is_writable()?
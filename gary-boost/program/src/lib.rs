@@ -1,5 +1,6 @@
 mod activate;
 mod claim;
+mod claim_to_stake;
 mod close;
 mod deactivate;
 mod deposit;
@@ -13,6 +14,7 @@ mod withdraw;
 
 use activate::*;
 use claim::*;
+use claim_to_stake::*;
 use close::*;
 use deactivate::*;
 use deposit::*;
@@ -42,6 +44,7 @@ pub fn process_instruction(
         BoostInstruction::Open => process_open(accounts, data)?,
         BoostInstruction::Rotate => process_rotate(accounts, data)?,
         BoostInstruction::Withdraw => process_withdraw(accounts, data)?,
+        BoostInstruction::ClaimToStake => process_claim_to_stake(accounts, data)?,
 
         // Admin
         BoostInstruction::Activate => process_activate(accounts, data)?,
@@ -64,7 +64,7 @@ pub fn process_new(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
 
     // Open a proof account for this boost.
     invoke_signed(
-        &gary_api::sdk::open(*boost_info.key, *boost_info.key, *signer_info.key),
+        &gary_api::sdk::open(*boost_info.key, *boost_info.key, *signer_info.key, None),
         &[
             boost_info.clone(),
             boost_info.clone(),
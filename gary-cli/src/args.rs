@@ -61,14 +61,21 @@ pub struct BenchmarkArgs {
         long,
         short,
         value_name = "THREAD_COUNT",
-        help = "The number of cores to use during the benchmark",
+        help = "The number of cores to use during the benchmark. CPU builds share one solver across them, which needs more than 20GB of memory.",
         default_value = "1"
     )]
     pub cores: String,
 }
 
 #[derive(Parser, Debug)]
-pub struct OpenArgs {}
+pub struct OpenArgs {
+    #[arg(
+        long,
+        value_name = "AUTHORITY",
+        help = "The authority of the miner who referred you."
+    )]
+    pub referrer: Option<String>,
+}
 
 #[derive(Parser, Debug)]
 pub struct BoostArgs {
@@ -118,6 +125,13 @@ pub struct ClaimArgs {
         help = "The optional pool url to claim rewards from."
     )]
     pub pool_url: Option<String>,
+
+    #[arg(
+        long,
+        help = "Stake the claimed rewards in the GARY boost instead of sending them to a wallet.",
+        conflicts_with_all = ["to", "pool_url"]
+    )]
+    pub stake: bool,
 }
 
 #[cfg(feature = "admin")]
@@ -130,7 +144,7 @@ pub struct MineArgs {
         long,
         short,
         value_name = "CORES_COUNT",
-        help = "The number of CPU cores to allocate to mining.",
+        help = "The number of CPU cores to allocate to mining. CPU builds share one solver across them, which needs more than 20GB of memory.",
         default_value = "1"
     )]
     pub cores: String,
@@ -9,7 +9,7 @@ use tabled::{Table, settings::{Style, Remove, object::{Rows, Columns}, Alignment
 
 use crate::{
     args::{AccountArgs, AccountCloseArgs, AccountCommand, ClaimArgs},
-    utils::{amount_u64_to_f64, ask_confirm, format_timestamp, get_proof, get_proof_with_authority, ComputeBudget, TableData, TableSectionTitle},
+    utils::{amount_u64_to_f64, ask_confirm, format_timestamp, get_proof, get_proof_extension, get_proof_with_authority, ComputeBudget, TableData, TableSectionTitle},
     Miner,
 };
 
@@ -163,6 +163,14 @@ impl Miner {
                 key: "Miner".to_string(),
                 value: proof.miner.to_string(),
             });
+            if let Ok(extension) = get_proof_extension(&self.rpc_client, proof_address).await {
+                if extension.referrer != Pubkey::default() {
+                    data.push(TableData {
+                        key: "Referrer".to_string(),
+                        value: extension.referrer.to_string(),
+                    });
+                }
+            }
         } else {
             data.push(TableData {
                 key: "Status".to_string(),
@@ -3,7 +3,7 @@ use std::{sync::Arc, time::Instant};
 use gary_drillx::equix;
 use solana_rpc_client::spinner;
 
-use crate::{args::BenchmarkArgs, Miner};
+use crate::{args::BenchmarkArgs, command::mine::NONCE_LOOPS, Miner};
 
 const TEST_DURATION: i64 = 30;
 
@@ -31,14 +31,22 @@ impl Miner {
                             .saturating_div(cores)
                             .saturating_mul(i.id as u64);
                         let mut nonce = first_nonce;
+
+                        // Return if core should not be used
+                        if (i.id as u64).ge(&cores) || i.id >= NONCE_LOOPS.unwrap_or(usize::MAX) {
+                            return 0;
+                        }
+
+                        // Init drillx. The CPU solver hashes on every benchmarked core itself.
+                        #[cfg(feature = "gpu")]
+                        let _ctx = cust::quick_init().unwrap();
+                        #[cfg(feature = "gpu")]
                         let mut memory = equix::SolverMemory::new().unwrap();
+                        #[cfg(not(feature = "gpu"))]
+                        let mut memory = equix::SolverMemory::with_threads(cores as usize);
                         loop {
-                            // Return if core should not be used
-                            if (i.id as u64).ge(&cores) {
-                                return 0;
-                            }
-
                             // Pin to core
+                            #[cfg(feature = "gpu")]
                             let _ = core_affinity::set_for_current(i);
 
                             // Create hash
@@ -2,6 +2,7 @@ use std::str::FromStr;
 
 use colored::*;
 use gary_api::consts::MINT_ADDRESS;
+use gary_boost_api::state::{boost_pda, stake_pda};
 use solana_program::pubkey::Pubkey;
 use solana_sdk::signature::{Signature, Signer};
 use spl_token::amount_to_ui_amount;
@@ -36,6 +37,10 @@ impl Miner {
         let signer = self.signer();
         let pubkey = signer.pubkey();
         let proof = get_proof_with_authority(&self.rpc_client, pubkey).await.expect("Failed to fetch proof account");
+        if args.stake {
+            self.claim_to_stake(args, proof.balance).await;
+            return;
+        }
         let mut ixs = vec![];
         let beneficiary = match args.to {
             None => self.initialize_ata(pubkey).await,
@@ -94,6 +99,52 @@ impl Miner {
             .ok();
     }
 
+    async fn claim_to_stake(&self, args: ClaimArgs, balance: u64) {
+        let signer = self.signer();
+        let pubkey = signer.pubkey();
+        let mut ixs = vec![];
+
+        // Parse amount to claim
+        let amount = if let Some(amount) = args.amount {
+            amount_f64_to_u64(amount)
+        } else {
+            balance
+        };
+
+        // Confirm user wants to claim
+        if !ask_confirm(
+            format!(
+                "\nYou are about to claim and stake {}.\n\nAre you sure you want to continue? [Y/n]",
+                format!(
+                    "{} GARY",
+                    amount_to_ui_amount(amount, gary_api::consts::TOKEN_DECIMALS)
+                )
+                .bold(),
+            )
+            .as_str(),
+        ) {
+            return;
+        }
+
+        // Open stake account, if needed
+        let boost_address = boost_pda(MINT_ADDRESS).0;
+        let stake_address = stake_pda(pubkey, boost_address).0;
+        if self
+            .rpc_client
+            .get_account_data(&stake_address)
+            .await
+            .is_err()
+        {
+            ixs.push(gary_boost_api::sdk::open(pubkey, pubkey, MINT_ADDRESS));
+        }
+
+        // Send and confirm
+        ixs.push(gary_boost_api::sdk::claim_to_stake(pubkey, amount));
+        self.send_and_confirm(&ixs, ComputeBudget::Fixed(100_000), false)
+            .await
+            .ok();
+    }
+
     async fn claim_from_pool(
         &self,
         args: ClaimArgs,
@@ -6,7 +6,6 @@ use std::{
     usize,
 };
 
-use b64::FromBase64;
 use colored::*;
 use crossterm::{
     cursor::MoveTo,
@@ -18,8 +17,8 @@ use gary_drillx::{
     Hash, Solution,
 };
 use gary_api::{
-    consts::{BUS_ADDRESSES, BUS_COUNT, EPOCH_DURATION},
-    event::MineEvent,
+    consts::MAX_BUNDLED_RESET_BUS_COUNT,
+    decode::{decode_events, DecodedEvent},
     state::{proof_pda, Bus, Config},
 };
 use rand::Rng;
@@ -38,18 +37,31 @@ use tabled::{
 };
 
 use crate::{
-    args::MineArgs,
+    args::{MineArgs, OpenArgs},
     error::Error,
     utils::{
         amount_u64_to_f64, format_duration, format_timestamp, get_boost_config, get_clock,
-        get_config, get_updated_proof_with_authority, ComputeBudget, PoolMiningData,
-        SoloMiningData,
+        get_config, get_proof_extension, get_updated_proof_with_authority, ComputeBudget,
+        PoolMiningData, SoloMiningData,
     },
     Miner,
 };
 
 use super::pool::Pool;
 
+/// The number of nonce loops to run per mining round, if not one per core.
+///
+/// The CPU solver holds every item of the search in memory, more than 20GB, and hashes them on
+/// all of the mining cores itself. So a single nonce loop drives one solver for the process.
+#[cfg(feature = "gpu")]
+pub(crate) const NONCE_LOOPS: Option<usize> = None;
+#[cfg(not(feature = "gpu"))]
+pub(crate) const NONCE_LOOPS: Option<usize> = Some(1);
+
+/// The CPU solver memory, kept for the lifetime of the process so it is only allocated once.
+#[cfg(not(feature = "gpu"))]
+static SOLVER_MEMORY: std::sync::Mutex<Option<equix::SolverMemory>> = std::sync::Mutex::new(None);
+
 impl Miner {
     pub async fn mine(&self, args: MineArgs) -> Result<(), Error> {
         match args.pool_url {
@@ -69,7 +81,7 @@ impl Miner {
 
     async fn mine_solo(&self, args: MineArgs) {
         // Open account, if needed.
-        self.open().await;
+        self.open(OpenArgs { referrer: None }).await;
 
         // Check num threads
         let cores_str = args.cores;
@@ -86,7 +98,11 @@ impl Miner {
         // Generate addresses
         let signer = self.signer();
         let _proof_address = proof_pda(signer.pubkey()).0;
-        let boost_config_address = gary_boost_api::state::config_pda().0;
+        let referrer_proof = get_proof_extension(&self.rpc_client, proof_pda(signer.pubkey()).0)
+            .await
+            .ok()
+            .map(|extension| extension.referrer)
+            .filter(|referrer| *referrer != Pubkey::default());
 
         // Start mining loop
         let mut last_hash_at = 0;
@@ -125,33 +141,35 @@ impl Miner {
             )
             .await;
 
-            // Build instruction set
-            let mut ixs = vec![gary_api::sdk::auth(proof_pda(signer.pubkey()).0)];
-            let mut compute_budget = 750_000;
-
             // Check for reset
-            if self.should_reset(config).await
+            let mut compute_budget = 750_000;
+            let mut reset = self.should_reset(config).await;
             // && rand::thread_rng().gen_range(0..100).eq(&0)
-            {
+            if reset && config.bus_count > MAX_BUNDLED_RESET_BUS_COUNT {
+                // Too many busses to reset alongside the mine, so reset on its own first
+                let reset_ix = gary_api::sdk::reset(signer.pubkey(), &config);
+                if let Err(err) = self
+                    .send_and_confirm(&[reset_ix], ComputeBudget::Fixed(100_000), false)
+                    .await
+                {
+                    println!("{}: {}", "ERROR".bold().red(), err);
+                }
+                reset = false;
+            }
+            if reset {
                 compute_budget += 100_000;
-                ixs.push(gary_api::sdk::reset(signer.pubkey()));
             }
 
-            // Build mine ix
+            // Build instruction set, with the boost rotation
             let boost_config = get_boost_config(&self.rpc_client).await;
-            let mine_ix = gary_api::sdk::mine(
-                signer.pubkey(),
+            let ixs = gary_boost_api::sdk::mine_instructions(
                 signer.pubkey(),
-                self.find_bus().await,
+                self.find_bus(config.bus_count).await,
                 solution,
                 boost_config.current,
-                boost_config_address,
+                referrer_proof,
+                reset.then_some(&config),
             );
-            ixs.push(mine_ix);
-
-            // Build rotation ix
-            let rotate_ix = gary_boost_api::sdk::rotate(signer.pubkey());
-            ixs.push(rotate_ix);
 
             // Submit transaction
             match self
@@ -290,7 +308,9 @@ impl Miner {
 
         progress_bar.set_message("Mining...");
         let core_ids = core_affinity::get_core_ids().expect("Failed to fetch core count");
-        let core_ids = core_ids.into_iter().filter(|id| id.id < (cores as usize));
+        let core_ids = core_ids
+            .into_iter()
+            .filter(|id| id.id < NONCE_LOOPS.unwrap_or(cores as usize));
         let handles: Vec<_> = core_ids
             .map(|i| {
                 let global_best_difficulty = Arc::clone(&global_best_difficulty);
@@ -300,6 +320,7 @@ impl Miner {
                     let pool_channel = pool_channel.clone();
                     move || {
                         // Pin to core
+                        #[cfg(feature = "gpu")]
                         let _ = core_affinity::set_for_current(i);
 
                         // Start hashing
@@ -310,12 +331,22 @@ impl Miner {
                         let mut best_hash = Hash::default();
 
                         // init drillx
+                        #[cfg(feature = "gpu")]
                         let _ctx = cust::quick_init().unwrap();
+                        #[cfg(feature = "gpu")]
                         let mut memory = equix::SolverMemory::new().unwrap();
+                        #[cfg(feature = "gpu")]
+                        let memory = &mut memory;
+                        #[cfg(not(feature = "gpu"))]
+                        let mut memory = SOLVER_MEMORY.lock().unwrap();
+                        #[cfg(not(feature = "gpu"))]
+                        let memory = memory.get_or_insert_with(|| {
+                            equix::SolverMemory::with_threads(cores as usize)
+                        });
                         loop {
                             // Get hashes
                             let hxs = gary_drillx::hashes_with_memory(
-                                &mut memory,
+                                memory,
                                 &challenge,
                                 &nonce.to_le_bytes(),
                             );
@@ -430,7 +461,7 @@ impl Miner {
             .expect("Failed to fetch clock account");
         config
             .last_reset_at
-            .saturating_add(EPOCH_DURATION as i64)
+            .saturating_add(config.epoch_duration())
             .saturating_sub(5) // Buffer
             .le(&clock.unix_timestamp)
     }
@@ -446,17 +477,18 @@ impl Miner {
             .max(0) as u64
     }
 
-    async fn find_bus(&self) -> Pubkey {
+    async fn find_bus(&self, bus_count: u64) -> Pubkey {
         // Fetch the bus with the largest balance
-        if let Ok(accounts) = self.rpc_client.get_multiple_accounts(&BUS_ADDRESSES).await {
+        let bus_addresses = gary_api::sdk::bus_addresses(bus_count);
+        if let Ok(accounts) = self.rpc_client.get_multiple_accounts(&bus_addresses).await {
             let mut top_bus_balance: u64 = 0;
-            let mut top_bus = BUS_ADDRESSES[0];
+            let mut top_bus = bus_addresses[0];
             for account in accounts {
                 if let Some(account) = account {
                     if let Ok(bus) = Bus::try_from_bytes(&account.data) {
                         if bus.rewards.gt(&top_bus_balance) {
                             top_bus_balance = bus.rewards;
-                            top_bus = BUS_ADDRESSES[bus.id as usize];
+                            top_bus = bus_addresses[bus.id as usize];
                         }
                     }
                 }
@@ -465,8 +497,8 @@ impl Miner {
         }
 
         // Otherwise return a random bus
-        let i = rand::thread_rng().gen_range(0..BUS_COUNT);
-        BUS_ADDRESSES[i]
+        let i = rand::thread_rng().gen_range(0..bus_addresses.len());
+        bus_addresses[i]
     }
 
     async fn fetch_solo_mine_event(&self, sig: Signature, verbose: bool) {
@@ -504,50 +536,46 @@ impl Miner {
         if let Ok(tx) = tx {
             if let Some(meta) = tx.transaction.meta {
                 if let OptionSerializer::Some(log_messages) = meta.log_messages {
-                    if let Some(return_log) = log_messages
-                        .iter()
-                        .find(|log| log.starts_with("Program return: "))
-                    {
-                        if let Some(return_data) =
-                            return_log.strip_prefix(&format!("Program return: {} ", gary_api::ID))
-                        {
-                            if let Ok(return_data) = return_data.from_base64() {
-                                let mut data = self.solo_mining_data.write().unwrap();
-                                let event = MineEvent::from_bytes(&return_data);
-                                let mining_data = SoloMiningData {
-                                    signature: if verbose {
-                                        sig.to_string()
-                                    } else {
-                                        format!("{}...", sig.to_string()[..8].to_string())
-                                    },
-                                    block: tx.slot.to_string(),
-                                    timestamp: format_timestamp(tx.block_time.unwrap_or_default()),
-                                    difficulty: event.difficulty.to_string(),
-                                    base_reward: if event.net_base_reward > 0 {
-                                        format!("{:#.11}", amount_u64_to_f64(event.net_base_reward))
-                                    } else {
-                                        "0".to_string()
-                                    },
-                                    boost_reward: if event.net_staker_boost_reward > 0 {
-                                        format!(
-                                            "{:#.11}",
-                                            amount_u64_to_f64(event.net_staker_boost_reward)
-                                        )
-                                    } else {
-                                        "0".to_string()
-                                    },
-                                    total_reward: if event.net_reward > 0 {
-                                        format!("{:#.11}", amount_u64_to_f64(event.net_reward))
-                                    } else {
-                                        "0".to_string()
-                                    },
-                                    timing: format!("{}s", event.timing),
-                                    status: "Confirmed".bold().green().to_string(),
-                                };
-                                data.remove(0);
-                                data.insert(0, mining_data);
-                            }
-                        }
+                    let mine_event = decode_events(&log_messages)
+                        .into_iter()
+                        .find_map(|event| match event {
+                            DecodedEvent::Mine(event) => Some(event),
+                            _ => None,
+                        });
+                    if let Some(event) = mine_event {
+                        let mut data = self.solo_mining_data.write().unwrap();
+                        let mining_data = SoloMiningData {
+                            signature: if verbose {
+                                sig.to_string()
+                            } else {
+                                format!("{}...", sig.to_string()[..8].to_string())
+                            },
+                            block: tx.slot.to_string(),
+                            timestamp: format_timestamp(tx.block_time.unwrap_or_default()),
+                            difficulty: event.difficulty.to_string(),
+                            base_reward: if event.net_base_reward > 0 {
+                                format!("{:#.11}", amount_u64_to_f64(event.net_base_reward))
+                            } else {
+                                "0".to_string()
+                            },
+                            boost_reward: if event.net_staker_boost_reward > 0 {
+                                format!(
+                                    "{:#.11}",
+                                    amount_u64_to_f64(event.net_staker_boost_reward)
+                                )
+                            } else {
+                                "0".to_string()
+                            },
+                            total_reward: if event.net_reward > 0 {
+                                format!("{:#.11}", amount_u64_to_f64(event.net_reward))
+                            } else {
+                                "0".to_string()
+                            },
+                            timing: format!("{}s", event.timing),
+                            status: "Confirmed".bold().green().to_string(),
+                        };
+                        data.remove(0);
+                        data.insert(0, mining_data);
                     }
                 }
             }
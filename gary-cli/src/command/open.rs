@@ -1,17 +1,31 @@
+use std::str::FromStr;
+
+use crate::args::OpenArgs;
 use crate::utils::ComputeBudget;
 use crate::Miner;
 use gary_api::prelude::proof_pda;
-use solana_sdk::signature::Signer;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
 
 impl Miner {
-    pub(crate) async fn open(&self) {
+    pub(crate) async fn open(&self, args: OpenArgs) {
+        // Parse referrer
+        let referrer_proof = args.referrer.map(|referrer| {
+            let referrer = Pubkey::from_str(&referrer).expect("Failed to parse referrer address");
+            proof_pda(referrer).0
+        });
+
         // Register miner
         let mut ixs = Vec::new();
         let signer_pubkey = self.signer().pubkey();
         let fee_payer = self.fee_payer();
         let proof_address = proof_pda(signer_pubkey).0;
         if self.rpc_client.get_account(&proof_address).await.is_err() {
-            let ix = gary_api::sdk::open(signer_pubkey, signer_pubkey, fee_payer.pubkey());
+            let ix = gary_api::sdk::open(
+                signer_pubkey,
+                signer_pubkey,
+                fee_payer.pubkey(),
+                referrer_proof,
+            );
             ixs.push(ix);
         }
 
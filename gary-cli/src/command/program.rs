@@ -1,4 +1,6 @@
-use gary_api::consts::{EPOCH_DURATION, BUS_ADDRESSES, TREASURY_TOKENS_ADDRESS, TREASURY_ADDRESS};
+use colored::Colorize;
+use gary_api::consts::{FEE_PERCENT_DENOMINATOR, PAUSE_CLAIM, PAUSE_MINE, PAUSE_RESET, TREASURY_TOKENS_ADDRESS, TREASURY_ADDRESS};
+use gary_api::state::RetargetMode;
 use tabled::{Table, settings::{Style, object::{Rows, Columns}, Alignment, Remove}};
 
 use crate::{utils::{get_config, amount_u64_to_f64, format_timestamp, get_bus, TableData, TableSectionTitle}, Miner};
@@ -30,9 +32,33 @@ impl Miner {
 
     async fn fetch_config_data(&self, data: &mut Vec<TableData>) {
         let config = get_config(&self.rpc_client).await;
+        data.push(TableData {
+            key: "Admin".to_string(),
+            value: config.admin.to_string(),
+        });
+        data.push(TableData {
+            key: "Guardian".to_string(),
+            value: config.guardian.to_string(),
+        });
+        data.push(TableData {
+            key: "Paused".to_string(),
+            value: if config.paused == 0 {
+                "None".to_string()
+            } else {
+                [(PAUSE_MINE, "Mine"), (PAUSE_CLAIM, "Claim"), (PAUSE_RESET, "Reset")]
+                    .iter()
+                    .filter(|(flag, _)| config.is_paused(*flag))
+                    .map(|(_, name)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+                    .bold()
+                    .red()
+                    .to_string()
+            },
+        });
         data.push(TableData {
             key: "Epoch duration".to_string(),
-            value: format!("{} sec", EPOCH_DURATION),
+            value: format!("{} sec", config.epoch_duration()),
         });
         data.push(TableData {
             key: "Epoch start at".to_string(),
@@ -42,10 +68,74 @@ impl Miner {
             key: "Min difficulty".to_string(),
             value: config.min_difficulty.to_string(),
         });
+        data.push(TableData {
+            key: "Tolerance".to_string(),
+            value: format!("{} sec", config.tolerance),
+        });
+        data.push(TableData {
+            key: "Smoothing factor".to_string(),
+            value: config.smoothing_factor.to_string(),
+        });
+        data.push(TableData {
+            key: "Base reward rate".to_string(),
+            value: format!(
+                "{} ({} - {})",
+                config.base_reward_rate,
+                config.base_reward_rate_min_threshold,
+                config.base_reward_rate_max_threshold
+            ),
+        });
+        data.push(TableData {
+            key: "Retarget mode".to_string(),
+            value: match config.retarget_mode() {
+                RetargetMode::Threshold => "Threshold".to_string(),
+                RetargetMode::Ema => format!("EMA ({} epoch window)", config.ema_window),
+            },
+        });
+        data.push(TableData {
+            key: "Reset reward".to_string(),
+            value: format!(
+                "{} GARY",
+                amount_u64_to_f64(config.reset_reward)
+            ),
+        });
+        data.push(TableData {
+            key: "Reward pool".to_string(),
+            value: format!(
+                "{} GARY ({}% of fees recycled)",
+                amount_u64_to_f64(config.reward_pool),
+                config.recycle_percent as f64 * 100.0 / FEE_PERCENT_DENOMINATOR as f64
+            ),
+        });
+        data.push(TableData {
+            key: "Referrals".to_string(),
+            value: format!(
+                "{}% of taxes for {} days",
+                config.referral_percent as f64 * 100.0 / FEE_PERCENT_DENOMINATOR as f64,
+                config.referral_window as f64 / 86_400.0
+            ),
+        });
+        data.push(TableData {
+            key: "Taxes".to_string(),
+            value: format!(
+                "{}% to {}",
+                config.taxes_percent as f64 * 100.0 / FEE_PERCENT_DENOMINATOR as f64,
+                config.taxes_address
+            ),
+        });
+        data.push(TableData {
+            key: "Fines".to_string(),
+            value: format!(
+                "{}% to {}",
+                config.fines_percent as f64 * 100.0 / FEE_PERCENT_DENOMINATOR as f64,
+                config.fines_address
+            ),
+        });
     }
 
     async fn fetch_busses_data(&self, data: &mut Vec<TableData>) {
-        for address in BUS_ADDRESSES.iter() {
+        let config = get_config(&self.rpc_client).await;
+        for address in gary_api::sdk::bus_addresses(config.bus_count).iter() {
             let bus = get_bus(&self.rpc_client, *address).await.expect("Failed to fetch bus account");
             let rewards = amount_u64_to_f64(bus.rewards);
             data.push(TableData {
@@ -1,8 +1,7 @@
 use std::str::FromStr;
 
-use b64::FromBase64;
 use colored::Colorize;
-use gary_api::event::MineEvent;
+use gary_api::decode::{decode_events, DecodedEvent};
 use solana_sdk::signature::Signature;
 use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionEncoding};
 use tabled::{settings::{object::{Columns, Rows}, Alignment, Remove, Style}, Table};
@@ -20,52 +19,52 @@ impl Miner {
                 // Parse transaction response
                 if let Some(meta) = tx.transaction.meta {
                     if let OptionSerializer::Some(log_messages) = meta.log_messages {
-                        if let Some(return_log) = log_messages.iter().find(|log| log.starts_with("Program return: ")) {
-                            if let Some(return_data) = return_log.strip_prefix(&format!("Program return: {} ", gary_api::ID)) {
-                                if let Ok(return_data) = return_data.from_base64() {
-                                    let event = MineEvent::from_bytes(&return_data);
-                                    data.push(TableData {
-                                        key: "Signature".to_string(),
-                                        value: signature.to_string(),
-                                    });
-                                    data.push(TableData {
-                                        key: "Block".to_string(),
-                                        value: tx.slot.to_string(),
-                                    });
-                                    data.push(TableData {
-                                        key: "Timestamp".to_string(),
-                                        value: format_timestamp(tx.block_time.unwrap_or_default()),
-                                    });
-                                    data.push(TableData {
-                                        key: "Difficulty".to_string(),
-                                        value: event.difficulty.to_string(),
-                                    });
-                                    data.push(TableData {
-                                        key: "Base Reward".to_string(),
-                                        value: amount_u64_to_string(event.net_base_reward),
-                                    });
-                                    data.push(TableData {
-                                        key: "Boost Reward".to_string(),
-                                        value: amount_u64_to_string(event.net_miner_boost_reward),
-                                    });
-                                    data.push(TableData {
-                                        key: "Total Reward".to_string(),
-                                        value: amount_u64_to_string(event.net_reward),
-                                    });
-                                    data.push(TableData {
-                                        key: "Timing".to_string(),
-                                        value: format!("{}s", event.timing),
-                                    });
-                                    data.push(TableData {
-                                        key: "Status".to_string(),
-                                        value: match meta.status {
-                                            Ok(()) => "Confirmed".bold().green().to_string(),
-                                            Err(_e) => "Failed".bold().red().to_string(),
-                                        },
-                                    });
-
-                                }
-                            }
+                        let mine_event = decode_events(&log_messages)
+                            .into_iter()
+                            .find_map(|event| match event {
+                                DecodedEvent::Mine(event) => Some(event),
+                                _ => None,
+                            });
+                        if let Some(event) = mine_event {
+                            data.push(TableData {
+                                key: "Signature".to_string(),
+                                value: signature.to_string(),
+                            });
+                            data.push(TableData {
+                                key: "Block".to_string(),
+                                value: tx.slot.to_string(),
+                            });
+                            data.push(TableData {
+                                key: "Timestamp".to_string(),
+                                value: format_timestamp(tx.block_time.unwrap_or_default()),
+                            });
+                            data.push(TableData {
+                                key: "Difficulty".to_string(),
+                                value: event.difficulty.to_string(),
+                            });
+                            data.push(TableData {
+                                key: "Base Reward".to_string(),
+                                value: amount_u64_to_string(event.net_base_reward),
+                            });
+                            data.push(TableData {
+                                key: "Boost Reward".to_string(),
+                                value: amount_u64_to_string(event.net_miner_boost_reward),
+                            });
+                            data.push(TableData {
+                                key: "Total Reward".to_string(),
+                                value: amount_u64_to_string(event.net_reward),
+                            });
+                            data.push(TableData {
+                                key: "Timing".to_string(),
+                                value: format!("{}s", event.timing),
+                            });
+                            data.push(TableData {
+                                key: "Status".to_string(),
+                                value: match meta.status {
+                                    Ok(()) => "Confirmed".bold().green().to_string(),
+                                    Err(_e) => "Failed".bold().red().to_string(),
+                                },
+                            });
                         }
                     }
                 }
@@ -211,7 +211,7 @@ async fn main() {
         Commands::Benchmark(args) => {
             miner.benchmark(args).await;
         }
-        Commands::Open(_) => miner.open().await,
+        Commands::Open(args) => miner.open(args).await,
         Commands::Claim(args) => {
             if let Err(err) = miner.claim(args).await {
                 println!("{:?}", err);
@@ -3,7 +3,7 @@ use std::time::Duration;
 use colored::Colorize;
 use gary_api::{
     consts::{CONFIG_ADDRESS, TREASURY_ADDRESS},
-    state::{proof_pda, Bus, Config, Proof, Treasury},
+    state::{proof_pda, Bus, Config, Proof, ProofExtension, Treasury},
 };
 use gary_boost_api::state::{Boost, Stake};
 use gary_pool_api::state::{Member, Pool};
@@ -181,7 +181,15 @@ pub async fn get_updated_proof_with_authority(
 
 pub async fn get_proof(client: &RpcClient, address: Pubkey) -> Result<Proof, anyhow::Error> {
     let data = client.get_account_data(&address).await?;
-    Ok(*Proof::try_from_bytes(&data)?)
+    Ok(*Proof::try_from_account_data(&data)?)
+}
+
+pub async fn get_proof_extension(
+    client: &RpcClient,
+    address: Pubkey,
+) -> Result<ProofExtension, anyhow::Error> {
+    let data = client.get_account_data(&address).await?;
+    Ok(*ProofExtension::try_from_account_data(&data)?)
 }
 
 pub async fn get_clock(client: &RpcClient) -> Result<Clock, anyhow::Error> {
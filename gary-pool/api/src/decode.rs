@@ -0,0 +1,191 @@
+pub use gary_api::decode::DecodeError;
+use gary_api::{
+    decode::{program_log_data, read_event, LogData},
+    event::MineEvent,
+};
+use gary_drillx::Solution;
+use steel::*;
+
+use crate::{event::UnstakeEvent, instruction::*};
+
+/// Decoded is a pool instruction with its args parsed and its accounts named.
+///
+/// Sysvars, programs, and GARY accounts passed through to the mine CPI are omitted.
+#[derive(Clone, Debug)]
+pub enum Decoded {
+    Claim {
+        signer: Pubkey,
+        beneficiary: Pubkey,
+        member: Pubkey,
+        pool: Pubkey,
+        amount: u64,
+    },
+    Join {
+        signer: Pubkey,
+        member_authority: Pubkey,
+        member: Pubkey,
+        pool: Pubkey,
+    },
+    Unstake {
+        signer: Pubkey,
+        boost: Pubkey,
+        mint: Pubkey,
+        member: Pubkey,
+        pool: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+    },
+    Attribute {
+        signer: Pubkey,
+        pool: Pubkey,
+        member: Pubkey,
+        total_balance: u64,
+    },
+    Launch {
+        signer: Pubkey,
+        miner: Pubkey,
+        pool: Pubkey,
+        proof: Pubkey,
+        url: [u8; 128],
+    },
+    Submit {
+        signer: Pubkey,
+        bus: Pubkey,
+        pool: Pubkey,
+        proof: Pubkey,
+        boost: Option<Pubkey>,
+        attestation: [u8; 32],
+        solution: Solution,
+    },
+
+    /// An instruction which the pool no longer processes, such as legacy staking and
+    /// migrations.
+    Deprecated(PoolInstruction),
+}
+
+/// Decode a pool instruction from its program id, data, and account keys.
+#[allow(deprecated)]
+pub fn decode_instruction(
+    program_id: &Pubkey,
+    data: &[u8],
+    accounts: &[Pubkey],
+) -> Result<Decoded, DecodeError> {
+    if program_id.ne(&crate::ID) {
+        return Err(DecodeError::InvalidProgramId);
+    }
+    let (tag, data) = data.split_first().ok_or(DecodeError::InvalidData)?;
+    let ix = PoolInstruction::try_from(*tag).or(Err(DecodeError::InvalidData))?;
+    let decoded = match ix {
+        PoolInstruction::Claim => {
+            let args = Claim::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, beneficiary, member, pool, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Claim {
+                signer,
+                beneficiary,
+                member,
+                pool,
+                amount: u64::from_le_bytes(args.amount),
+            }
+        }
+        PoolInstruction::Join => {
+            let [signer, member_authority, member, pool, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Join {
+                signer,
+                member_authority,
+                member,
+                pool,
+            }
+        }
+        PoolInstruction::Unstake => {
+            let args = Unstake::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, boost, _boost_tokens, mint, member, pool, _pool_tokens, recipient, ..] =
+                *accounts
+            else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Unstake {
+                signer,
+                boost,
+                mint,
+                member,
+                pool,
+                recipient,
+                amount: u64::from_le_bytes(args.amount),
+            }
+        }
+        PoolInstruction::Attribute => {
+            let args = Attribute::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, pool, _pool_tokens, _proof, member, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Attribute {
+                signer,
+                pool,
+                member,
+                total_balance: u64::from_le_bytes(args.total_balance),
+            }
+        }
+        PoolInstruction::Launch => {
+            let args = Launch::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, miner, pool, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Launch {
+                signer,
+                miner,
+                pool,
+                proof,
+                url: args.url,
+            }
+        }
+        PoolInstruction::Submit => {
+            let args = Submit::try_from_bytes(data).or(Err(DecodeError::InvalidData))?;
+            let [signer, bus, _config, pool, proof, ..] = *accounts else {
+                return Err(DecodeError::NotEnoughAccounts);
+            };
+            Decoded::Submit {
+                signer,
+                bus,
+                pool,
+                proof,
+                boost: accounts.get(9).copied(),
+                attestation: args.attestation,
+                solution: Solution::new(args.digest, args.nonce),
+            }
+        }
+        PoolInstruction::OpenShare
+        | PoolInstruction::Stake
+        | PoolInstruction::Commit
+        | PoolInstruction::OpenStake
+        | PoolInstruction::MigratePool
+        | PoolInstruction::MigrateMemberBalance
+        | PoolInstruction::QuickMigrate => Decoded::Deprecated(ix),
+    };
+    Ok(decoded)
+}
+
+/// DecodedEvent is an event logged by the pool program.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecodedEvent {
+    /// The mine event of a submission, returned by the GARY mine CPI.
+    Mine(MineEvent),
+    Unstake(UnstakeEvent),
+}
+
+/// Decode every event the pool program logged in a transaction, in log order.
+pub fn decode_events(logs: &[String]) -> Vec<DecodedEvent> {
+    program_log_data(logs, &crate::ID)
+        .into_iter()
+        .filter_map(|data| match data {
+            LogData::Data(data) => read_event(&data).map(DecodedEvent::Unstake),
+            LogData::Return(setter, data) if setter.eq(&gary_api::ID) => {
+                read_event(&data).map(DecodedEvent::Mine)
+            }
+            LogData::Return(..) => None,
+        })
+        .collect()
+}
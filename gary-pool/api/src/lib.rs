@@ -1,4 +1,5 @@
 pub mod consts;
+pub mod decode;
 pub mod error;
 pub mod event;
 #[allow(deprecated)]
@@ -21,7 +21,7 @@ pub fn process_attribute(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramRe
         .as_account_mut::<Pool>(&gary_pool_api::ID)?
         .assert_mut(|p| p.authority == *signer_info.key)?;
     let proof = proof_info
-        .as_account::<Proof>(&gary_api::ID)?
+        .as_proof()?
         .assert(|p| p.authority == *pool_info.key)?;
     let member = member_info
         .as_account_mut::<Member>(&gary_pool_api::ID)?
@@ -25,7 +25,7 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         .assert_mut(|m| m.pool == *pool_info.key)?;
     let pool = pool_info.as_account_mut::<Pool>(&gary_pool_api::ID)?;
     proof_info
-        .as_account::<Proof>(&gary_api::ID)?
+        .as_proof()?
         .assert(|p| p.authority == *pool_info.key)?;
     treasury_info.has_address(&gary_api::consts::TREASURY_ADDRESS)?;
     treasury_tokens_info.has_address(&gary_api::consts::TREASURY_TOKENS_ADDRESS)?;
@@ -30,7 +30,7 @@ pub fn process_launch(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
     // Open proof account.
     if proof_info.is_empty().is_ok() {
         invoke_signed(
-            &gary_api::sdk::open(*pool_info.key, *miner_info.key, *signer_info.key),
+            &gary_api::sdk::open(*pool_info.key, *miner_info.key, *signer_info.key, None),
             &[
                 pool_info.clone(),
                 miner_info.clone(),
@@ -45,7 +45,7 @@ pub fn process_launch(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
     }
 
     // Initialize pool account.
-    let proof = proof_info.as_account::<Proof>(&gary_api::ID)?;
+    let proof = proof_info.as_proof()?;
     if pool_info.is_empty().is_ok() {
         create_program_account::<Pool>(
             pool_info,
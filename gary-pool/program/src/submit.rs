@@ -21,7 +21,7 @@ pub fn process_submit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
         .assert_mut(|p| p.authority == *signer_info.key)?;
     let proof = proof_info
         .is_writable()?
-        .as_account::<Proof>(&gary_api::ID)?
+        .as_proof()?
         .assert(|p| p.authority == *pool_info.key)?;
     gary_program.is_program(&gary_api::ID)?;
     system_program.is_program(&system_program::ID)?;
@@ -52,6 +52,7 @@ pub fn process_submit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResul
             solution,
             *boost_info.key,
             *boost_config_info.key,
+            None,
         ),
         &mine_accounts,
     )?;
@@ -1,9 +1,6 @@
 use std::collections::HashMap;
 
-use gary_api::{
-    consts::{BUS_ADDRESSES, BUS_COUNT},
-    state::{proof_pda, Bus},
-};
+use gary_api::state::{proof_pda, Bus};
 use gary_pool_types::Challenge;
 use rand::Rng;
 use sha3::{Digest, Sha3_256};
@@ -401,15 +398,17 @@ impl Aggregator {
     /// fetch the bus with the largest balance
     async fn find_bus(&self, operator: &Operator) -> Result<Pubkey, Error> {
         let rpc_client = &operator.rpc_client;
-        let accounts = rpc_client.get_multiple_accounts(&BUS_ADDRESSES).await?;
+        let config = operator.get_config().await?;
+        let bus_addresses = gary_api::sdk::bus_addresses(config.bus_count);
+        let accounts = rpc_client.get_multiple_accounts(&bus_addresses).await?;
         let mut top_bus_balance: u64 = 0;
-        let bus_index = rand::thread_rng().gen_range(0..BUS_COUNT);
-        let mut top_bus = BUS_ADDRESSES[bus_index];
+        let bus_index = rand::thread_rng().gen_range(0..bus_addresses.len());
+        let mut top_bus = bus_addresses[bus_index];
         for account in accounts.into_iter().flatten() {
             if let Ok(bus) = Bus::try_from_bytes(&account.data) {
                 if bus.rewards.gt(&top_bus_balance) {
                     top_bus_balance = bus.rewards;
-                    top_bus = BUS_ADDRESSES[bus.id as usize];
+                    top_bus = bus_addresses[bus.id as usize];
                 }
             }
         }
@@ -6,6 +6,8 @@ pub enum Error {
     Bincode(#[from] bincode::Error),
     #[error("base64 decode")]
     Base64Decode(#[from] base64::DecodeError),
+    #[error("instruction decode")]
+    InstructionDecode(#[from] gary_pool_api::decode::DecodeError),
     #[error("try from slice")]
     TryFromSlice(#[from] std::array::TryFromSliceError),
     #[error("rewards channel send")]
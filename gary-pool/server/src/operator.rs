@@ -90,7 +90,7 @@ impl Operator {
         let (pool_pda, _) = gary_pool_api::state::pool_pda(authority);
         let (proof_pda, _) = gary_pool_api::state::pool_proof_pda(pool_pda);
         let data = rpc_client.get_account_data(&proof_pda).await?;
-        let proof = Proof::try_from_bytes(data.as_slice())?;
+        let proof = Proof::try_from_account_data(data.as_slice())?;
         Ok(*proof)
     }
 
@@ -123,7 +123,7 @@ impl Operator {
         Ok(())
     }
 
-    async fn get_config(&self) -> Result<Config, Error> {
+    pub async fn get_config(&self) -> Result<Config, Error> {
         let config_pda = gary_api::consts::CONFIG_ADDRESS;
         let rpc_client = &self.rpc_client;
         let data = rpc_client.get_account_data(&config_pda).await?;
@@ -1,9 +1,9 @@
 use crate::tx::submit::JITO_TIP_ADDRESSES;
-use gary_pool_api::{instruction::Attribute, prelude::PoolInstruction};
+use gary_pool_api::decode::{decode_instruction, DecodeError, Decoded};
 use solana_sdk::pubkey;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::{
-    program_error::ProgramError, system_instruction, system_program, transaction::Transaction,
+    instruction::CompiledInstruction, system_instruction, system_program, transaction::Transaction,
 };
 use spl_associated_token_account::ID as SPL_ASSOCIATED_TOKEN_ID;
 
@@ -73,36 +73,31 @@ pub fn validate_attribution(
     }
 
     // Validate that it's specifically an attribution instruction
-    let attr_data = attr_ix.data.as_slice();
-    let (attr_tag, attr_data) = attr_data
-        .split_first()
-        .ok_or(ProgramError::InvalidInstructionData)?;
-    let attr_tag =
-        PoolInstruction::try_from(*attr_tag).or(Err(ProgramError::InvalidInstructionData))?;
-    if attr_tag.ne(&PoolInstruction::Attribute) {
+    let attr_accounts = instruction_accounts(transaction, attr_ix)?;
+    let Decoded::Attribute {
+        member,
+        total_balance: args_total_balance,
+        ..
+    } = decode_instruction(attr_program_id, &attr_ix.data, &attr_accounts).map_err(|err| {
+        match err {
+            DecodeError::NotEnoughAccounts => Error::MemberDoesNotExist,
+            err => err.into(),
+        }
+    })?
+    else {
         return Err(Error::Internal(
             "first instruction after compute budget and lighthouse instructions must be an attribution instruction".to_string(),
         ));
-    }
+    };
 
     // Validate attribution amount
-    let args = Attribute::try_from_bytes(attr_data)?;
-    let args_total_balance = u64::from_le_bytes(args.total_balance);
     if args_total_balance.gt(&(total_balance as u64)) {
         return Err(Error::Internal("invalid total balance arg".to_string()));
     }
 
     // Validate attribution member authority
-    //
-    // The fifth account in the attribution instruction is the member account
-    let member_authority_index = attr_ix.accounts.get(4).ok_or(Error::MemberDoesNotExist)?;
-    let member_parsed = transaction
-        .message
-        .account_keys
-        .get((*member_authority_index) as usize)
-        .ok_or(Error::MemberDoesNotExist)?;
     let (member_pda, _) = gary_pool_api::state::member_pda(member_authority, pool);
-    if member_pda.ne(member_parsed) {
+    if member_pda.ne(&member) {
         return Err(Error::Internal(
             "payload and instruction member accounts do not match".to_string(),
         ));
@@ -143,13 +138,9 @@ pub fn validate_attribution(
         // If the instruction is from gary_pool, validate it as a claim instruction
         if claim_program_id.eq(&gary_pool_api::id()) {
             // Validate as specifically a claim instruction
-            let claim_data = claim_ix.data.as_slice();
-            let (claim_tag, _claim_data) = claim_data
-                .split_first()
-                .ok_or(ProgramError::InvalidInstructionData)?;
-            let claim_tag = PoolInstruction::try_from(*claim_tag)
-                .or(Err(ProgramError::InvalidInstructionData))?;
-            if claim_tag.ne(&PoolInstruction::Claim) {
+            let claim_accounts = instruction_accounts(transaction, claim_ix)?;
+            let decoded = decode_instruction(claim_program_id, &claim_ix.data, &claim_accounts)?;
+            if !matches!(decoded, Decoded::Claim { .. }) {
                 return Err(Error::Internal(
                     "gary_pool instruction after attribution must be a claim instruction"
                         .to_string(),
@@ -213,3 +204,21 @@ pub fn validate_attribution(
 
     Ok(())
 }
+
+/// Resolve the account keys of a compiled instruction.
+fn instruction_accounts(
+    transaction: &Transaction,
+    ix: &CompiledInstruction,
+) -> Result<Vec<Pubkey>, Error> {
+    ix.accounts
+        .iter()
+        .map(|i| {
+            transaction
+                .message
+                .account_keys
+                .get(*i as usize)
+                .copied()
+                .ok_or(Error::Internal("missing instruction account".to_string()))
+        })
+        .collect()
+}
@@ -1,8 +1,8 @@
 use std::{collections::HashMap, str::FromStr};
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
-use base64::{prelude::BASE64_STANDARD, Engine};
 use cached::proc_macro::cached;
+use gary_pool_api::decode::{decode_events, DecodedEvent};
 use solana_sdk::signature::Signature;
 
 use crate::{contributions::PoolMiningEvent, error::Error};
@@ -89,26 +89,16 @@ pub async fn mine_event(
 fn parse_mine_event(
     payload: &RawPayload,
 ) -> Result<gary_api::event::MineEvent, Error> {
-    // Find return data string
+    // Find the mine event returned by the pool submission
     let log_messages = payload.meta.log_messages.as_slice();
-    let prefix = format!("Program return: {} ", gary_pool_api::ID.to_string());
-    let mut mine_event_str = "";
-    for log_message in log_messages.iter().rev() {
-        if log_message.starts_with(&prefix) {
-            mine_event_str = log_message.trim_start_matches(&prefix);
-            break;
-        }
-    }
-    if mine_event_str.is_empty() {
-        return Err(Error::Internal("webhook event missing return data".to_string()));
-    }
-
-    // Parse return data 
-    let mine_event = BASE64_STANDARD.decode(mine_event_str)?;
-    let mine_event: &gary_api::event::MineEvent =
-        bytemuck::try_from_bytes(mine_event.as_slice())
-            .map_err(|e| Error::Internal(e.to_string()))?;
-    Ok(*mine_event)
+    decode_events(log_messages)
+        .into_iter()
+        .rev()
+        .find_map(|event| match event {
+            DecodedEvent::Mine(event) => Some(event),
+            _ => None,
+        })
+        .ok_or(Error::Internal("webhook event missing return data".to_string()))
 }
 
 /// Validate the auth header
@@ -0,0 +1,41 @@
+use actix_web::HttpResponse;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("instruction decode")]
+    InstructionDecode(#[from] gary_api::decode::DecodeError),
+    #[error("std io")]
+    StdIO(#[from] std::io::Error),
+    #[error("std env")]
+    StdEnv(#[from] std::env::VarError),
+    #[error("std parse int")]
+    StdParseInt(#[from] std::num::ParseIntError),
+    #[error("solana client")]
+    SolanaClient(#[from] solana_client::client_error::ClientError),
+    #[error("solana program")]
+    SolanaProgram(#[from] solana_sdk::program_error::ProgramError),
+    #[error("solana signer")]
+    SolanaSigner(#[from] solana_sdk::signer::SignerError),
+    #[error("solana transaction")]
+    SolanaTransaction(#[from] solana_sdk::transaction::TransactionError),
+    #[error("solana sanitize")]
+    SolanaSanitize(#[from] solana_sdk::sanitize::SanitizeError),
+    #[error("invalid transaction: {0}")]
+    InvalidTransaction(String),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl From<Error> for HttpResponse {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::InstructionDecode(_)
+            | Error::SolanaTransaction(_)
+            | Error::SolanaSanitize(_)
+            | Error::InvalidTransaction(_) => HttpResponse::BadRequest().body(value.to_string()),
+            // The preflight simulation failed, such as for a spam or too easy hash.
+            Error::SolanaClient(err) => HttpResponse::UnprocessableEntity().body(err.to_string()),
+            _ => HttpResponse::InternalServerError().finish(),
+        }
+    }
+}
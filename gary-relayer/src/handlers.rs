@@ -0,0 +1,30 @@
+use actix_web::{web, HttpResponse, Responder};
+use gary_api::state::proof_pda;
+use solana_sdk::signer::Signer;
+
+use crate::{
+    relayer::Relayer,
+    types::{MinePayload, MineResponse, RelayerAddress},
+};
+
+pub async fn address(relayer: web::Data<Relayer>) -> impl Responder {
+    let address = relayer.keypair.pubkey();
+    HttpResponse::Ok().json(&RelayerAddress {
+        address,
+        proof: proof_pda(address).0,
+        fee_bps: relayer.fee_bps,
+    })
+}
+
+/// Accepts mine transactions signed by miners. If they pay the relayer fee,
+/// signs them as fee payer and submits them.
+pub async fn mine(relayer: web::Data<Relayer>, payload: web::Json<MinePayload>) -> impl Responder {
+    match relayer.relay(payload.into_inner().transaction).await {
+        Ok(signature) => HttpResponse::Ok().json(&MineResponse { signature }),
+        Err(err) => {
+            log::error!("{:?}", err);
+            let http_response: HttpResponse = err.into();
+            http_response
+        }
+    }
+}
@@ -0,0 +1,40 @@
+mod error;
+mod handlers;
+mod relayer;
+mod types;
+mod validate;
+
+use actix_web::{get, middleware, web, App, HttpResponse, HttpServer, Responder};
+use relayer::Relayer;
+
+#[actix_web::main]
+async fn main() -> Result<(), error::Error> {
+    env_logger::init();
+    let relayer = web::Data::new(Relayer::new()?);
+
+    // The relayer is paid into its own proof, so it must be opened first
+    if let Err(err) = relayer.get_proof().await {
+        log::error!("relayer proof not found, open it with the relayer keypair");
+        return Err(err);
+    }
+
+    // launch server
+    HttpServer::new(move || {
+        log::info!("starting relayer server");
+        App::new()
+            .wrap(middleware::Logger::default())
+            .app_data(relayer.clone())
+            .service(web::resource("/address").route(web::get().to(handlers::address)))
+            .service(web::resource("/mine").route(web::post().to(handlers::mine)))
+            .service(health)
+    })
+    .bind("0.0.0.0:3000")?
+    .run()
+    .await
+    .map_err(From::from)
+}
+
+#[get("/health")]
+async fn health() -> impl Responder {
+    HttpResponse::Ok().body("ok")
+}
@@ -0,0 +1,111 @@
+use gary_api::state::{proof_pda, Proof};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    sanitize::Sanitize,
+    signature::{Keypair, Signature},
+    signer::{EncodableKey, Signer},
+    transaction::Transaction,
+};
+
+use crate::{error::Error, validate::validate_mine};
+
+pub struct Relayer {
+    /// The relayer keypair, which pays the transaction fees.
+    pub keypair: Keypair,
+
+    /// Solana RPC client.
+    pub rpc_client: RpcClient,
+
+    /// The minimum relayer fee in basis points.
+    /// Applied to the miner reward of every relayed hash.
+    pub fee_bps: u64,
+
+    /// The maximum compute unit limit.
+    pub max_compute_unit_limit: u32,
+
+    /// The maximum compute unit price in micro-lamports.
+    pub max_compute_unit_price: u64,
+}
+
+impl Relayer {
+    pub fn new() -> Result<Relayer, Error> {
+        let keypair = Self::keypair()?;
+        let rpc_client = Self::rpc_client()?;
+        let fee_bps = Self::fee_bps()?;
+        let max_compute_unit_limit = Self::max_compute_unit_limit()?;
+        let max_compute_unit_price = Self::max_compute_unit_price()?;
+        log::info!("relayer fee bps: {}", fee_bps);
+        Ok(Relayer {
+            keypair,
+            rpc_client,
+            fee_bps,
+            max_compute_unit_limit,
+            max_compute_unit_price,
+        })
+    }
+
+    /// Validates a mine transaction signed by the miner, then signs it as
+    /// fee payer and submits it. The rpc simulates the transaction first,
+    /// so the relayer does not pay for hashes which would fail.
+    pub async fn relay(&self, mut transaction: Transaction) -> Result<Signature, Error> {
+        transaction.sanitize()?;
+        validate_mine(
+            &transaction,
+            &self.keypair.pubkey(),
+            self.fee_bps,
+            self.max_compute_unit_limit,
+            self.max_compute_unit_price,
+        )?;
+        let blockhash = transaction.message.recent_blockhash;
+        transaction.try_partial_sign(&[&self.keypair], blockhash)?;
+        transaction.verify()?;
+        let signature = self.rpc_client.send_transaction(&transaction).await?;
+        Ok(signature)
+    }
+
+    pub async fn get_proof(&self) -> Result<Proof, Error> {
+        let proof_pda = proof_pda(self.keypair.pubkey()).0;
+        let data = self.rpc_client.get_account_data(&proof_pda).await?;
+        let proof = Proof::try_from_account_data(data.as_slice())?;
+        Ok(*proof)
+    }
+
+    fn keypair() -> Result<Keypair, Error> {
+        let keypair_path = std::env::var("KEYPAIR_PATH")?;
+        let keypair = Keypair::read_from_file(keypair_path)
+            .map_err(|err| Error::Internal(err.to_string()))?;
+        Ok(keypair)
+    }
+
+    fn rpc_client() -> Result<RpcClient, Error> {
+        let rpc_url = std::env::var("RPC_URL")?;
+        Ok(RpcClient::new_with_commitment(
+            rpc_url,
+            CommitmentConfig::confirmed(),
+        ))
+    }
+
+    fn fee_bps() -> Result<u64, Error> {
+        let str = std::env::var("FEE_BPS")?;
+        let fee_bps: u64 = str.parse()?;
+        if fee_bps > gary_api::consts::MAX_RELAYER_FEE_BPS {
+            return Err(Error::Internal(
+                "fee bps is above the maximum relayer fee".to_string(),
+            ));
+        }
+        Ok(fee_bps)
+    }
+
+    fn max_compute_unit_limit() -> Result<u32, Error> {
+        let str = std::env::var("MAX_COMPUTE_UNIT_LIMIT")?;
+        let limit: u32 = str.parse()?;
+        Ok(limit)
+    }
+
+    fn max_compute_unit_price() -> Result<u64, Error> {
+        let str = std::env::var("MAX_COMPUTE_UNIT_PRICE")?;
+        let price: u64 = str.parse()?;
+        Ok(price)
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RelayerAddress {
+    /// The relayer keypair, which must be the fee payer of relayed transactions.
+    pub address: Pubkey,
+
+    /// The relayer proof, which must be passed to the mine relayed instruction.
+    pub proof: Pubkey,
+
+    /// The minimum fee the relayer accepts, in basis points of the miner reward.
+    pub fee_bps: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MinePayload {
+    /// The mine transaction, with the relayer as fee payer
+    /// and signed by the miner.
+    pub transaction: Transaction,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MineResponse {
+    /// The signature of the submitted transaction.
+    pub signature: Signature,
+}
@@ -0,0 +1,287 @@
+use gary_api::{
+    consts::NOOP_PROGRAM_ID,
+    decode::{decode_instruction, Decoded},
+    state::proof_pda,
+};
+use solana_sdk::{
+    compute_budget, instruction::CompiledInstruction, pubkey::Pubkey, transaction::Transaction,
+};
+
+use crate::error::Error;
+
+/// The compute budget instruction tag which sets the compute unit limit.
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+/// The compute budget instruction tag which sets the compute unit price.
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// The relayer as fee payer, and the miner.
+const MAX_SIGNERS: u8 = 2;
+
+/// Validates a transaction relays exactly one hash, and pays the relayer at least its fee.
+///
+/// The relayer signs as fee payer, so it must not be passed to any instruction. Otherwise a
+/// miner could spend its lamports, such as with a system transfer.
+pub fn validate_mine(
+    transaction: &Transaction,
+    relayer: &Pubkey,
+    min_fee_bps: u64,
+    max_compute_unit_limit: u32,
+    max_compute_unit_price: u64,
+) -> Result<(), Error> {
+    let message = &transaction.message;
+
+    // Relayer must be the fee payer
+    if message.account_keys.first().ne(&Some(relayer)) {
+        return Err(invalid("the relayer must be the fee payer"));
+    }
+    if message.header.num_required_signatures > MAX_SIGNERS {
+        return Err(invalid("too many signers"));
+    }
+
+    // Only compute budget, auth, and mine relayed instructions are allowed
+    let mut num_mines = 0;
+    for ix in &message.instructions {
+        if ix.accounts.contains(&0) {
+            return Err(invalid("the relayer may only be the fee payer"));
+        }
+        let program_id = message
+            .account_keys
+            .get(ix.program_id_index as usize)
+            .ok_or(invalid("missing program id"))?;
+        if program_id.eq(&compute_budget::id()) {
+            validate_compute_budget(ix, max_compute_unit_limit, max_compute_unit_price)?;
+        } else if program_id.eq(&gary_api::ID) {
+            let accounts = instruction_accounts(transaction, ix)?;
+            let Decoded::MineRelayed {
+                relayer_proof,
+                fee_bps,
+                ..
+            } = decode_instruction(program_id, &ix.data, &accounts)?
+            else {
+                return Err(invalid("only mine relayed instructions can be relayed"));
+            };
+            if relayer_proof.ne(&proof_pda(*relayer).0) {
+                return Err(invalid("the relayer proof does not belong to the relayer"));
+            }
+            if fee_bps < min_fee_bps {
+                return Err(invalid("the relayer fee is below the minimum"));
+            }
+            num_mines += 1;
+        } else if program_id.ne(&NOOP_PROGRAM_ID) {
+            return Err(invalid("unexpected program"));
+        }
+    }
+    if num_mines != 1 {
+        return Err(invalid(
+            "transaction must contain exactly one mine relayed instruction",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_compute_budget(
+    ix: &CompiledInstruction,
+    max_compute_unit_limit: u32,
+    max_compute_unit_price: u64,
+) -> Result<(), Error> {
+    match ix.data.first() {
+        Some(&SET_COMPUTE_UNIT_LIMIT) => {
+            let limit = ix
+                .data
+                .get(1..5)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u32::from_le_bytes)
+                .ok_or(invalid("invalid compute unit limit"))?;
+            if limit > max_compute_unit_limit {
+                return Err(invalid("the compute unit limit is above the maximum"));
+            }
+        }
+        Some(&SET_COMPUTE_UNIT_PRICE) => {
+            let price = ix
+                .data
+                .get(1..9)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u64::from_le_bytes)
+                .ok_or(invalid("invalid compute unit price"))?;
+            if price > max_compute_unit_price {
+                return Err(invalid("the compute unit price is above the maximum"));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn instruction_accounts(
+    transaction: &Transaction,
+    ix: &CompiledInstruction,
+) -> Result<Vec<Pubkey>, Error> {
+    ix.accounts
+        .iter()
+        .map(|i| {
+            transaction
+                .message
+                .account_keys
+                .get(*i as usize)
+                .copied()
+                .ok_or(invalid("missing instruction account"))
+        })
+        .collect()
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::InvalidTransaction(msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use gary_api::{consts::BUS_ADDRESSES, sdk, state::proof_pda};
+    use gary_drillx::Solution;
+    use solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        transaction::Transaction,
+    };
+
+    use super::validate_mine;
+    use crate::error::Error;
+
+    const MIN_FEE_BPS: u64 = 500;
+    const MAX_COMPUTE_UNIT_LIMIT: u32 = 800_000;
+    const MAX_COMPUTE_UNIT_PRICE: u64 = 100_000;
+
+    fn mine_relayed(miner: Pubkey, relayer: Pubkey, fee_bps: u64) -> Instruction {
+        sdk::mine_relayed(
+            miner,
+            miner,
+            BUS_ADDRESSES[0],
+            Solution::new(Default::default(), [0; 8]),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            relayer,
+            fee_bps,
+            None,
+        )
+    }
+
+    fn relay_ixs(miner: Pubkey, relayer: Pubkey) -> Vec<Instruction> {
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT),
+            ComputeBudgetInstruction::set_compute_unit_price(MAX_COMPUTE_UNIT_PRICE),
+            sdk::auth(proof_pda(miner).0),
+            mine_relayed(miner, relayer, MIN_FEE_BPS),
+        ]
+    }
+
+    fn validate(ixs: &[Instruction], fee_payer: &Pubkey, relayer: &Pubkey) -> Result<(), Error> {
+        let transaction = Transaction::new_with_payer(ixs, Some(fee_payer));
+        validate_mine(
+            &transaction,
+            relayer,
+            MIN_FEE_BPS,
+            MAX_COMPUTE_UNIT_LIMIT,
+            MAX_COMPUTE_UNIT_PRICE,
+        )
+    }
+
+    fn assert_invalid(result: Result<(), Error>, expected: &str) {
+        match result {
+            Err(Error::InvalidTransaction(msg)) => assert_eq!(msg, expected),
+            result => panic!("expected {:?}, got {:?}", expected, result),
+        }
+    }
+
+    #[test]
+    fn test_validate_mine() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(validate(&relay_ixs(miner, relayer), &relayer, &relayer).is_ok());
+    }
+
+    #[test]
+    fn test_validate_mine_wrong_fee_payer() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_invalid(
+            validate(&relay_ixs(miner, relayer), &miner, &relayer),
+            "the relayer must be the fee payer",
+        );
+    }
+
+    #[test]
+    fn test_validate_mine_extra_signer() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut ixs = relay_ixs(miner, relayer);
+        ixs[2]
+            .accounts
+            .push(AccountMeta::new_readonly(Pubkey::new_unique(), true));
+        assert_invalid(validate(&ixs, &relayer, &relayer), "too many signers");
+    }
+
+    #[test]
+    fn test_validate_mine_relayer_account() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut ixs = relay_ixs(miner, relayer);
+        ixs[2].accounts.push(AccountMeta::new(relayer, false));
+        assert_invalid(
+            validate(&ixs, &relayer, &relayer),
+            "the relayer may only be the fee payer",
+        );
+    }
+
+    #[test]
+    fn test_validate_mine_foreign_program() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut ixs = relay_ixs(miner, relayer);
+        ixs.push(Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            vec![],
+        ));
+        assert_invalid(validate(&ixs, &relayer, &relayer), "unexpected program");
+    }
+
+    #[test]
+    fn test_validate_mine_two_mines() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut ixs = relay_ixs(miner, relayer);
+        ixs.push(mine_relayed(miner, relayer, MIN_FEE_BPS));
+        assert_invalid(
+            validate(&ixs, &relayer, &relayer),
+            "transaction must contain exactly one mine relayed instruction",
+        );
+    }
+
+    #[test]
+    fn test_validate_mine_fee_below_minimum() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut ixs = relay_ixs(miner, relayer);
+        ixs[3] = mine_relayed(miner, relayer, MIN_FEE_BPS - 1);
+        assert_invalid(
+            validate(&ixs, &relayer, &relayer),
+            "the relayer fee is below the minimum",
+        );
+    }
+
+    #[test]
+    fn test_validate_mine_compute_unit_limit_above_maximum() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut ixs = relay_ixs(miner, relayer);
+        ixs[0] = ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT + 1);
+        assert_invalid(
+            validate(&ixs, &relayer, &relayer),
+            "the compute unit limit is above the maximum",
+        );
+    }
+
+    #[test]
+    fn test_validate_mine_compute_unit_price_above_maximum() {
+        let (miner, relayer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut ixs = relay_ixs(miner, relayer);
+        ixs[1] = ComputeBudgetInstruction::set_compute_unit_price(MAX_COMPUTE_UNIT_PRICE + 1);
+        assert_invalid(
+            validate(&ixs, &relayer, &relayer),
+            "the compute unit price is above the maximum",
+        );
+    }
+}
@@ -0,0 +1,470 @@
+pub mod idl;
+pub mod parse;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+use syn::Type;
+
+use crate::{
+    idl::*,
+    parse::{EnumDef, SourceFile, StructDef},
+};
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// The source directories of a program, relative to the repository root.
+pub struct ProgramSource {
+    /// The program name used in the IDL and its file name.
+    pub name: &'static str,
+
+    /// The api crate, which holds the instructions, state, errors, events, and sdk.
+    pub api: &'static str,
+
+    /// The program crate, which holds the instruction processors.
+    pub program: &'static str,
+}
+
+/// The programs to generate IDLs for.
+pub const PROGRAMS: [ProgramSource; 3] = [
+    ProgramSource {
+        name: "gary",
+        api: "api",
+        program: "program",
+    },
+    ProgramSource {
+        name: "gary_boost",
+        api: "gary-boost/api",
+        program: "gary-boost/program",
+    },
+    ProgramSource {
+        name: "gary_pool",
+        api: "gary-pool/api",
+        program: "gary-pool/program",
+    },
+];
+
+/// The directory the IDLs are written to, relative to the repository root.
+pub const IDL_DIR: &str = "idl";
+
+/// Returns the path of a program's IDL file.
+pub fn idl_path(root: &Path, program: &ProgramSource) -> PathBuf {
+    root.join(IDL_DIR).join(format!("{}.json", program.name))
+}
+
+/// Generates the IDL of a program from its sources, and renders it as JSON.
+pub fn generate_json(root: &Path, program: &ProgramSource) -> Result<String> {
+    let idl = generate(root, program)?;
+    Ok(serde_json::to_string_pretty(&idl)? + "\n")
+}
+
+/// Generates the IDL of a program from its sources.
+///
+/// Instructions come from the `instruction!` invocations, with account metas taken from the
+/// first sdk function which builds each instruction. Account layouts come from the `account!`
+/// invocations, events from `event!`, and errors from `error!`.
+pub fn generate(root: &Path, program: &ProgramSource) -> Result<Idl> {
+    let api_dir = root.join(program.api);
+    let files = parse::parse_dir(&api_dir.join("src"))?;
+    let processors = parse::parse_dir(&root.join(program.program).join("src"))?;
+
+    // Index the api sources.
+    let mut consts = HashMap::new();
+    let mut enums = vec![];
+    let mut ix_structs = vec![];
+    let mut structs = vec![];
+    let mut state_names = vec![];
+    for source in files.iter() {
+        consts.extend(parse::consts(&source.file));
+        enums.extend(parse::enums(&source.file)?);
+        if source.path == Path::new("instruction.rs") {
+            ix_structs.extend(parse::structs(&source.file));
+        } else {
+            let file_structs = parse::structs(&source.file);
+            if source.path.starts_with("state") {
+                state_names.extend(file_structs.iter().map(|s| s.name.clone()));
+            }
+            structs.extend(file_structs);
+        }
+    }
+    let find_enum = |name: &str| -> Result<&EnumDef> {
+        enums
+            .iter()
+            .find(|e| e.name == name)
+            .ok_or_else(|| format!("enum {} not found", name).into())
+    };
+    let mut process_docs = HashMap::new();
+    for source in processors.iter() {
+        process_docs.extend(parse::fn_docs(&source.file));
+    }
+
+    // Build the instructions.
+    let ix_macros = macro_args_of(&files, "instruction")?;
+    let ix_names: Vec<String> = ix_macros.iter().map(|args| args[1].clone()).collect();
+    let mut sdk_metas = HashMap::new();
+    for source in files.iter().filter(|s| s.path == Path::new("sdk.rs")) {
+        for (ix, metas) in parse::sdk_instructions(&source.file, &ix_names) {
+            sdk_metas.entry(ix).or_insert(metas);
+        }
+    }
+    let mut instructions = vec![];
+    for args in ix_macros.iter() {
+        let (enum_name, name) = (&args[0], &args[1]);
+        let variant = find_enum(enum_name)?
+            .variants
+            .iter()
+            .find(|v| v.name == *name)
+            .ok_or(format!("{}::{} not found", enum_name, name))?;
+        let data = ix_structs
+            .iter()
+            .find(|s| s.name == *name)
+            .ok_or(format!("instruction data {} not found", name))?;
+        let (accounts, remaining_accounts) = match sdk_metas.remove(name) {
+            Some(metas) => instruction_accounts(metas),
+            None => (vec![], vec![]),
+        };
+        let snake_name = snake_case(name);
+        instructions.push(Instruction {
+            docs: process_docs
+                .get(&format!("process_{}", snake_name))
+                .cloned()
+                .unwrap_or_default(),
+            name: snake_name,
+            discriminator: vec![variant.value as u8],
+            accounts,
+            remaining_accounts,
+            args: fields(data, &consts)?,
+        });
+    }
+    instructions.sort_by_key(|ix| ix.discriminator[0]);
+
+    // Build the accounts.
+    let mut accounts = vec![];
+    for args in macro_args_of(&files, "account")? {
+        let (enum_name, name) = (&args[0], &args[1]);
+        let variant = find_enum(enum_name)?
+            .variants
+            .iter()
+            .find(|v| v.name == *name)
+            .ok_or(format!("{}::{} not found", enum_name, name))?;
+        let mut discriminator = vec![0u8; 8];
+        discriminator[0] = variant.value as u8;
+        accounts.push(Account {
+            name: name.clone(),
+            discriminator,
+        });
+    }
+    accounts.sort_by_key(|account| account.discriminator[0]);
+
+    // Build the events.
+    //
+    // Events with a leading `disc` field are discriminated by the event enum variant of the
+    // same name.
+    let mut events = vec![];
+    for args in macro_args_of(&files, "event")? {
+        let name = &args[0];
+        let has_disc = structs
+            .iter()
+            .find(|s| s.name == *name)
+            .and_then(|s| s.fields.first())
+            .is_some_and(|f| f.name == "disc");
+        let discriminator = enums
+            .iter()
+            .flat_map(|e| e.variants.iter())
+            .find(|v| has_disc && format!("{}Event", v.name) == *name)
+            .map_or(vec![], |v| v.value.to_le_bytes().to_vec());
+        events.push(Event {
+            name: name.clone(),
+            discriminator,
+        });
+    }
+
+    // Build the errors.
+    let mut errors = vec![];
+    for args in macro_args_of(&files, "error")? {
+        for variant in find_enum(&args[0])?.variants.iter() {
+            errors.push(Error {
+                code: variant.value as u32,
+                name: variant.name.clone(),
+                msg: variant.msg.clone(),
+            });
+        }
+    }
+
+    // Build the types of every account, state, and event, and the types they reference.
+    let mut types: Vec<TypeDef> = vec![];
+    let mut pending: Vec<String> = accounts
+        .iter()
+        .map(|a| a.name.clone())
+        .chain(state_names)
+        .chain(events.iter().map(|e| e.name.clone()))
+        .collect();
+    pending.reverse();
+    while let Some(name) = pending.pop() {
+        if types.iter().any(|t| t.name == name) {
+            continue;
+        }
+        let type_def = match structs.iter().find(|s| s.name == name) {
+            Some(s) => type_def(&s.name, s.docs.clone(), fields(s, &consts)?),
+            None => builtin_type(&name).ok_or(format!("type {} not found", name))?,
+        };
+        for field in type_def.ty.fields.iter() {
+            pending.extend(defined_names(&field.ty));
+        }
+        types.push(type_def);
+    }
+
+    Ok(Idl {
+        address: files
+            .iter()
+            .find(|s| s.path == Path::new("lib.rs"))
+            .and_then(|s| parse::declared_id(&s.file))
+            .ok_or("program id not found")?,
+        metadata: Metadata {
+            name: program.name.to_string(),
+            version: crate_version(&api_dir)?,
+            spec: IDL_SPEC.to_string(),
+        },
+        instructions,
+        accounts,
+        events,
+        errors,
+        types,
+    })
+}
+
+fn macro_args_of(files: &[SourceFile], name: &str) -> Result<Vec<Vec<String>>> {
+    let mut args = vec![];
+    for source in files.iter() {
+        args.extend(parse::macro_args(&source.file, name)?);
+    }
+    Ok(args)
+}
+
+/// Splits the sdk account metas into the fixed and the remaining accounts, naming any account
+/// whose name could not be derived by its position.
+fn instruction_accounts(
+    metas: Vec<parse::AccountMetaDef>,
+) -> (Vec<InstructionAccount>, Vec<InstructionAccount>) {
+    let mut accounts: Vec<InstructionAccount> = vec![];
+    let mut remaining_accounts = vec![];
+    for (i, meta) in metas.into_iter().enumerate() {
+        let mut name = meta.name.unwrap_or(format!("account_{}", i));
+        if accounts.iter().any(|a| a.name == name) {
+            name = format!("{}_{}", name, i);
+        }
+        let account = InstructionAccount {
+            name,
+            writable: meta.writable,
+            signer: meta.signer,
+            optional: meta.optional,
+        };
+        if meta.remaining {
+            remaining_accounts.push(account);
+        } else {
+            accounts.push(account);
+        }
+    }
+    (accounts, remaining_accounts)
+}
+
+fn fields(s: &StructDef, consts: &HashMap<String, u64>) -> Result<Vec<Field>> {
+    s.fields
+        .iter()
+        .map(|field| {
+            Ok(Field {
+                name: field.name.clone(),
+                docs: field.docs.clone(),
+                ty: idl_type(&field.ty, consts)
+                    .ok_or(format!("{}.{} has an unsupported type", s.name, field.name))?,
+            })
+        })
+        .collect()
+}
+
+fn type_def(name: &str, docs: Vec<String>, fields: Vec<Field>) -> TypeDef {
+    TypeDef {
+        name: name.to_string(),
+        docs,
+        serialization: "bytemuck".to_string(),
+        repr: json!({ "kind": "c" }),
+        ty: TypeDefTy {
+            kind: "struct".to_string(),
+            fields,
+        },
+    }
+}
+
+/// Types defined outside of the api crates.
+fn builtin_type(name: &str) -> Option<TypeDef> {
+    match name {
+        "Numeric" => Some(type_def(
+            name,
+            vec!["A signed 80.48 fixed point number.".to_string()],
+            vec![Field {
+                name: "bits".to_string(),
+                docs: vec![],
+                ty: json!({ "array": ["u8", 16] }),
+            }],
+        )),
+        _ => None,
+    }
+}
+
+/// Maps a Rust field type to an IDL type.
+fn idl_type(ty: &Type, consts: &HashMap<String, u64>) -> Option<Value> {
+    match ty {
+        Type::Path(ty) => {
+            let name = ty.path.segments.last()?.ident.to_string();
+            Some(match name.as_str() {
+                "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64" | "i128"
+                | "bool" => json!(name),
+                "Pubkey" => json!("pubkey"),
+                "HashDigest" => json!({ "array": ["u8", 32] }),
+                _ => json!({ "defined": { "name": name } }),
+            })
+        }
+        Type::Array(ty) => {
+            let len = match &ty.len {
+                syn::Expr::Lit(expr) => match &expr.lit {
+                    syn::Lit::Int(lit) => lit.base10_parse().ok()?,
+                    _ => return None,
+                },
+                syn::Expr::Path(expr) => {
+                    *consts.get(&expr.path.segments.last()?.ident.to_string())?
+                }
+                _ => return None,
+            };
+            Some(json!({ "array": [idl_type(&ty.elem, consts)?, len] }))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the names of the defined types an IDL type references.
+fn defined_names(ty: &Value) -> Vec<String> {
+    if let Some(name) = ty.pointer("/defined/name").and_then(Value::as_str) {
+        return vec![name.to_string()];
+    }
+    match ty.get("array").and_then(|a| a.get(0)) {
+        Some(elem) => defined_names(elem),
+        None => vec![],
+    }
+}
+
+/// Reads the version of a crate, falling back to its workspace version.
+fn crate_version(dir: &Path) -> Result<String> {
+    for manifest in dir.ancestors().map(|d| d.join("Cargo.toml")) {
+        let Ok(manifest) = fs::read_to_string(manifest) else {
+            continue;
+        };
+        let version = manifest.lines().find_map(|line| {
+            line.strip_prefix("version = \"")
+                .and_then(|v| v.strip_suffix('"'))
+        });
+        if let Some(version) = version {
+            return Ok(version.to_string());
+        }
+    }
+    Err(format!("version of {} not found", dir.display()).into())
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::*;
+
+    fn root() -> &'static Path {
+        Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+    }
+
+    #[test]
+    fn test_idls_are_up_to_date() {
+        for program in PROGRAMS.iter() {
+            let json = generate_json(root(), program).unwrap();
+            let path = idl_path(root(), program);
+            assert_eq!(
+                fs::read_to_string(&path).unwrap_or_default(),
+                json,
+                "{} is out of date, run `cargo run -p gary-idl`",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_gary_idl() {
+        let idl = generate(root(), &PROGRAMS[0]).unwrap();
+        assert_eq!(idl.address, gary_api::ID.to_string());
+
+        // Account metas follow the sdk.
+        let claim = idl
+            .instructions
+            .iter()
+            .find(|ix| ix.name == "claim")
+            .unwrap();
+        let names: Vec<&str> = claim.accounts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "signer",
+                "beneficiary",
+                "proof",
+                "treasury",
+                "treasury_tokens",
+                "token_program"
+            ]
+        );
+        assert!(claim.accounts[0].signer && claim.accounts[0].writable);
+        assert!(!claim.accounts[3].writable);
+
+        // Busses are passed to reset after the fixed accounts.
+        let reset = idl
+            .instructions
+            .iter()
+            .find(|ix| ix.name == "reset")
+            .unwrap();
+        assert_eq!(reset.remaining_accounts.len(), 1);
+        assert_eq!(reset.remaining_accounts[0].name, "bus");
+
+        // Accounts carry the steel discriminator in the first byte.
+        let proof = idl.accounts.iter().find(|a| a.name == "Proof").unwrap();
+        assert_eq!(proof.discriminator, [102, 0, 0, 0, 0, 0, 0, 0]);
+
+        // Events with a leading disc field are discriminated.
+        let claim_event = idl.events.iter().find(|e| e.name == "ClaimEvent").unwrap();
+        assert_eq!(claim_event.discriminator, 0u64.to_le_bytes());
+        let mine_event = idl.events.iter().find(|e| e.name == "MineEvent").unwrap();
+        assert!(mine_event.discriminator.is_empty());
+
+        // Nested types and the proof extension are included.
+        assert!(idl.types.iter().any(|t| t.name == "EpochSummary"));
+        assert!(idl.types.iter().any(|t| t.name == "ProofExtension"));
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("ClaimToStake"), "claim_to_stake");
+        assert_eq!(snake_case("Mine"), "mine");
+    }
+}
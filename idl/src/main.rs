@@ -0,0 +1,36 @@
+use std::{fs, path::Path, process::exit};
+
+use gary_idl::{generate_json, idl_path, PROGRAMS};
+
+/// Writes the IDL of every program to the `idl` directory. With `--check`, exits with an error
+/// instead if any IDL is out of date.
+fn main() {
+    let check = std::env::args().any(|arg| arg == "--check");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let mut stale = false;
+    for program in PROGRAMS.iter() {
+        let json = match generate_json(root, program) {
+            Ok(json) => json,
+            Err(err) => {
+                eprintln!("failed to generate the {} idl: {}", program.name, err);
+                exit(1);
+            }
+        };
+        let path = idl_path(root, program);
+        if fs::read_to_string(&path).is_ok_and(|current| current == json) {
+            continue;
+        }
+        if check {
+            eprintln!("{} is out of date", path.display());
+            stale = true;
+        } else if let Err(err) = fs::write(&path, json) {
+            eprintln!("failed to write {}: {}", path.display(), err);
+            exit(1);
+        } else {
+            println!("wrote {}", path.display());
+        }
+    }
+    if stale {
+        exit(1);
+    }
+}
@@ -0,0 +1,534 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use syn::{
+    punctuated::Punctuated,
+    visit::{self, Visit},
+    Attribute, Expr, Fields, Ident, Item, Lit, LitStr, Member, Meta, Token, Type,
+};
+
+use crate::Result;
+
+/// A source file of an api crate.
+pub struct SourceFile {
+    /// The path of the file, relative to the `src` directory.
+    pub path: PathBuf,
+
+    /// The parsed file.
+    pub file: syn::File,
+}
+
+/// An enum with explicit or implicit discriminants.
+pub struct EnumDef {
+    pub name: String,
+    pub variants: Vec<VariantDef>,
+}
+
+pub struct VariantDef {
+    pub name: String,
+    pub value: u64,
+    pub docs: Vec<String>,
+
+    /// The message of an `#[error("...")]` attribute.
+    pub msg: Option<String>,
+}
+
+/// A struct with named fields.
+pub struct StructDef {
+    pub name: String,
+    pub docs: Vec<String>,
+    pub fields: Vec<FieldDef>,
+}
+
+pub struct FieldDef {
+    pub name: String,
+    pub docs: Vec<String>,
+    pub ty: Type,
+}
+
+/// An account meta built by an sdk function.
+#[derive(Debug, PartialEq)]
+pub struct AccountMetaDef {
+    /// The account name, derived from the expression of the account address.
+    pub name: Option<String>,
+    pub writable: bool,
+    pub signer: bool,
+
+    /// True if the account is only added in some branch of the sdk function.
+    pub optional: bool,
+
+    /// True if the account is one of a variable number of accounts built by a closure.
+    pub remaining: bool,
+}
+
+/// Parses every `.rs` file under a directory, in path order.
+pub fn parse_dir(dir: &Path) -> Result<Vec<SourceFile>> {
+    let mut paths = vec![];
+    collect_paths(dir, &mut paths)?;
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let file = syn::parse_file(&fs::read_to_string(&path)?)
+                .map_err(|err| format!("{}: {}", path.display(), err))?;
+            Ok(SourceFile {
+                path: path.strip_prefix(dir)?.to_path_buf(),
+                file,
+            })
+        })
+        .collect()
+}
+
+fn collect_paths(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_paths(&path, paths)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the doc comments of an item.
+pub fn docs(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(s) => Some(s.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the ident arguments of every item-level invocation of the named macro, such as
+/// `account!(GaryAccount, Proof)`.
+pub fn macro_args(file: &syn::File, name: &str) -> Result<Vec<Vec<String>>> {
+    let mut args = vec![];
+    for item in file.items.iter() {
+        if let Item::Macro(item) = item {
+            if item.mac.path.is_ident(name) {
+                let idents = item
+                    .mac
+                    .parse_body_with(Punctuated::<Ident, Token![,]>::parse_terminated)?;
+                args.push(idents.iter().map(|i| i.to_string()).collect());
+            }
+        }
+    }
+    Ok(args)
+}
+
+/// Returns the program id declared with `declare_id!`.
+pub fn declared_id(file: &syn::File) -> Option<String> {
+    file.items.iter().find_map(|item| match item {
+        Item::Macro(item) if item.mac.path.is_ident("declare_id") => {
+            item.mac.parse_body::<LitStr>().ok().map(|s| s.value())
+        }
+        _ => None,
+    })
+}
+
+/// Returns the integer constants of a file.
+pub fn consts(file: &syn::File) -> HashMap<String, u64> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Const(item) => Some((item.ident.to_string(), int_lit(&item.expr)?)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the fieldless enums of a file, such as the instruction, event, and error enums.
+pub fn enums(file: &syn::File) -> Result<Vec<EnumDef>> {
+    let mut enums = vec![];
+    for item in file.items.iter() {
+        let Item::Enum(item) = item else {
+            continue;
+        };
+        if item.variants.iter().any(|v| !matches!(v.fields, Fields::Unit)) {
+            continue;
+        }
+        let mut variants: Vec<VariantDef> = vec![];
+        for variant in item.variants.iter() {
+            let value = match &variant.discriminant {
+                Some((_, expr)) => int_lit(expr).ok_or(format!(
+                    "{}::{} has an invalid discriminant",
+                    item.ident, variant.ident
+                ))?,
+                None => variants.last().map_or(0, |v| v.value + 1),
+            };
+            let msg = variant
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("error"))
+                .map(|attr| attr.parse_args::<LitStr>())
+                .transpose()?
+                .map(|s| s.value());
+            variants.push(VariantDef {
+                name: variant.ident.to_string(),
+                value,
+                docs: docs(&variant.attrs),
+                msg,
+            });
+        }
+        enums.push(EnumDef {
+            name: item.ident.to_string(),
+            variants,
+        });
+    }
+    Ok(enums)
+}
+
+/// Returns the structs with named or no fields of a file.
+pub fn structs(file: &syn::File) -> Vec<StructDef> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item) => Some(StructDef {
+                name: item.ident.to_string(),
+                docs: docs(&item.attrs),
+                fields: match &item.fields {
+                    Fields::Named(fields) => fields
+                        .named
+                        .iter()
+                        .map(|field| FieldDef {
+                            name: field.ident.as_ref().unwrap().to_string(),
+                            docs: docs(&field.attrs),
+                            ty: field.ty.clone(),
+                        })
+                        .collect(),
+                    Fields::Unit => vec![],
+                    Fields::Unnamed(_) => return None,
+                },
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the doc comments of each function in a file, by name.
+pub fn fn_docs(file: &syn::File) -> HashMap<String, Vec<String>> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(item) => Some((item.sig.ident.to_string(), docs(&item.attrs))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the instruction built by each sdk function, with the account metas it passes.
+///
+/// Only functions which build an `Instruction` literal from one of the given instruction data
+/// types, either constructed in place or passed as an argument, are returned.
+pub fn sdk_instructions(
+    file: &syn::File,
+    ix_names: &[String],
+) -> Vec<(String, Vec<AccountMetaDef>)> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(item) => {
+                let arg_types = item
+                    .sig
+                    .inputs
+                    .iter()
+                    .filter_map(|input| match input {
+                        syn::FnArg::Typed(arg) => match (&*arg.pat, &*arg.ty) {
+                            (syn::Pat::Ident(pat), Type::Path(ty)) => Some((
+                                pat.ident.to_string(),
+                                ty.path.segments.last()?.ident.to_string(),
+                            )),
+                            _ => None,
+                        },
+                        syn::FnArg::Receiver(_) => None,
+                    })
+                    .collect();
+                let mut visitor = SdkVisitor {
+                    ix_names,
+                    arg_types,
+                    ix: None,
+                    metas: vec![],
+                    closures: 0,
+                    branches: 0,
+                    remaining_name: None,
+                };
+                visitor.visit_item_fn(item);
+                Some((visitor.ix?, visitor.metas))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+struct SdkVisitor<'a> {
+    ix_names: &'a [String],
+    arg_types: HashMap<String, String>,
+    ix: Option<String>,
+    metas: Vec<AccountMetaDef>,
+    closures: usize,
+    branches: usize,
+    remaining_name: Option<String>,
+}
+
+impl<'ast> Visit<'ast> for SdkVisitor<'_> {
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        if path_idents(&expr.path)
+            .last()
+            .is_some_and(|i| i == "Instruction")
+        {
+            for field in expr.fields.iter() {
+                if matches!(&field.member, Member::Named(ident) if ident == "data") {
+                    let mut data = DataVisitor {
+                        ix_names: self.ix_names,
+                        arg_types: &self.arg_types,
+                        ix: None,
+                    };
+                    data.visit_expr(&field.expr);
+                    self.ix = self.ix.take().or(data.ix);
+                }
+            }
+        }
+        visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_expr_call(&mut self, expr: &'ast syn::ExprCall) {
+        if let Expr::Path(func) = &*expr.func {
+            let idents = path_idents(&func.path);
+            let writable = match idents.as_slice() {
+                [.., ty, f] if ty == "AccountMeta" && f == "new" => Some(true),
+                [.., ty, f] if ty == "AccountMeta" && f == "new_readonly" => Some(false),
+                _ => None,
+            };
+            if let (Some(writable), Some(address), Some(signer)) =
+                (writable, expr.args.first(), expr.args.get(1))
+            {
+                let remaining = self.closures > 0;
+                let name = if remaining {
+                    self.remaining_name.clone()
+                } else {
+                    account_name(address)
+                };
+                self.metas.push(AccountMetaDef {
+                    name,
+                    writable,
+                    signer: matches!(signer, Expr::Lit(lit) if matches!(&lit.lit, Lit::Bool(b) if b.value)),
+                    optional: self.branches > 0,
+                    remaining,
+                });
+            }
+        }
+        visit::visit_expr_call(self, expr);
+    }
+
+    fn visit_expr_method_call(&mut self, expr: &'ast syn::ExprMethodCall) {
+        if expr.method == "map" && matches!(expr.args.first(), Some(Expr::Closure(_))) {
+            self.remaining_name = account_name(&expr.receiver);
+        }
+        visit::visit_expr_method_call(self, expr);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // Macro bodies are not parsed by syn, so parse the elements of `vec![...]` here.
+        if mac.path.is_ident("vec") {
+            if let Ok(exprs) = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+            {
+                for expr in exprs.iter() {
+                    self.visit_expr(expr);
+                }
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+
+    fn visit_expr_closure(&mut self, expr: &'ast syn::ExprClosure) {
+        self.closures += 1;
+        visit::visit_expr_closure(self, expr);
+        self.closures -= 1;
+    }
+
+    fn visit_expr_if(&mut self, expr: &'ast syn::ExprIf) {
+        self.branches += 1;
+        visit::visit_expr_if(self, expr);
+        self.branches -= 1;
+    }
+
+    fn visit_expr_match(&mut self, expr: &'ast syn::ExprMatch) {
+        self.branches += 1;
+        visit::visit_expr_match(self, expr);
+        self.branches -= 1;
+    }
+}
+
+struct DataVisitor<'a> {
+    ix_names: &'a [String],
+    arg_types: &'a HashMap<String, String>,
+    ix: Option<String>,
+}
+
+impl DataVisitor<'_> {
+    fn found(&mut self, name: Option<&String>) {
+        if let Some(name) = name {
+            if self.ix.is_none() && self.ix_names.contains(name) {
+                self.ix = Some(name.clone());
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for DataVisitor<'_> {
+    fn visit_expr_struct(&mut self, expr: &'ast syn::ExprStruct) {
+        self.found(path_idents(&expr.path).last());
+        visit::visit_expr_struct(self, expr);
+    }
+
+    fn visit_expr_path(&mut self, expr: &'ast syn::ExprPath) {
+        if let Some(ident) = expr.path.get_ident() {
+            self.found(self.arg_types.get(&ident.to_string()));
+        }
+        visit::visit_expr_path(self, expr);
+    }
+}
+
+/// Derives an account name from the expression of its address, such as `proof` from
+/// `proof_pda(signer).0` or `token_program` from `spl_token::ID`.
+pub fn account_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Path(expr) => {
+            let idents = path_idents(&expr.path);
+            match idents.as_slice() {
+                [.., program, id] if id == "ID" || id == "id" => {
+                    if idents.iter().any(|i| i == "sysvar") {
+                        return Some(format!("{}_sysvar", program));
+                    }
+                    let program = program.trim_start_matches("spl_").trim_end_matches("_api");
+                    if program.ends_with("_program") {
+                        Some(program.to_string())
+                    } else {
+                        Some(format!("{}_program", program))
+                    }
+                }
+                [.., name] if name.chars().all(|c| !c.is_lowercase()) => {
+                    let name = name
+                        .trim_end_matches("_ADDRESS")
+                        .trim_end_matches("_ID")
+                        .to_lowercase();
+
+                    // Prefix the constants of other programs with the program name.
+                    match idents.first().and_then(|i| i.strip_suffix("_api")) {
+                        Some(program) => Some(format!("{}_{}", program, name)),
+                        None => Some(name),
+                    }
+                }
+                [.., name] => Some(strip_suffixes(name)),
+                [] => None,
+            }
+        }
+        Expr::Field(expr) => match &expr.member {
+            Member::Named(ident) => Some(strip_suffixes(&ident.to_string())),
+            Member::Unnamed(_) => account_name(&expr.base),
+        },
+        Expr::Call(expr) => {
+            let Expr::Path(func) = &*expr.func else {
+                return None;
+            };
+            let func = path_idents(&func.path).pop()?;
+            if let Some(base) = func.strip_suffix("_addresses") {
+                return Some(base.to_string());
+            }
+            let base = func.strip_suffix("_pda")?;
+            match expr.args.first().and_then(account_name) {
+                Some(arg) if arg != "signer" && arg != "authority" && !arg.starts_with(base) => {
+                    Some(format!("{}_{}", arg, base))
+                }
+                _ => Some(base.to_string()),
+            }
+        }
+        Expr::Index(expr) => {
+            let base = account_name(&expr.expr)?;
+            let index = int_lit(&expr.index)?;
+            let base = strip_suffixes(base.strip_suffix('s').unwrap_or(&base));
+            Some(format!("{}_{}", base, index))
+        }
+        Expr::Cast(expr) => account_name(&expr.expr),
+        Expr::Paren(expr) => account_name(&expr.expr),
+        Expr::Reference(expr) => account_name(&expr.expr),
+        Expr::MethodCall(expr) => account_name(&expr.receiver),
+        _ => None,
+    }
+}
+
+fn strip_suffixes(name: &str) -> String {
+    let stripped = name.trim_end_matches("_pda").trim_end_matches("_address");
+    if stripped.is_empty() {
+        name.to_string()
+    } else {
+        stripped.to_string()
+    }
+}
+
+fn path_idents(path: &syn::Path) -> Vec<String> {
+    path.segments.iter().map(|s| s.ident.to_string()).collect()
+}
+
+fn int_lit(expr: &Expr) -> Option<u64> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Int(lit) => lit.base10_parse().ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::account_name;
+
+    fn name(expr: &str) -> Option<String> {
+        account_name(&syn::parse_str(expr).unwrap())
+    }
+
+    #[test]
+    fn test_account_name() {
+        assert_eq!(name("signer").unwrap(), "signer");
+        assert_eq!(name("proof_pda(signer).0").unwrap(), "proof");
+        assert_eq!(name("proof_pda(boost).0").unwrap(), "boost_proof");
+        assert_eq!(
+            name("proof_pda(config.taxes_address).0").unwrap(),
+            "taxes_proof"
+        );
+        assert_eq!(name("bus_pda(bus_id as u8).0").unwrap(), "bus");
+        assert_eq!(name("bus_pdas[3].0").unwrap(), "bus_3");
+        assert_eq!(name("config_pda().0").unwrap(), "config");
+        assert_eq!(name("boost_proof_address").unwrap(), "boost_proof");
+        assert_eq!(name("CONFIG_ADDRESS").unwrap(), "config");
+        assert_eq!(
+            name("gary_api::consts::TREASURY_TOKENS_ADDRESS").unwrap(),
+            "gary_treasury_tokens"
+        );
+        assert_eq!(name("spl_token::ID").unwrap(), "token_program");
+        assert_eq!(name("gary_api::ID").unwrap(), "gary_program");
+        assert_eq!(name("system_program::ID").unwrap(), "system_program");
+        assert_eq!(
+            name("sysvar::slot_hashes::ID").unwrap(),
+            "slot_hashes_sysvar"
+        );
+        assert_eq!(
+            name("bus_addresses(config.bus_count).into_iter()").unwrap(),
+            "bus"
+        );
+        assert!(name("[0; 32]").is_none());
+    }
+}
@@ -0,0 +1,29 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// AcceptAdmin completes a pending admin transfer.
+pub fn process_accept_admin(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .is_config()?
+        .is_writable()?
+        .as_account_mut::<Config>(&gary_api::ID)?
+        .assert_mut(|c| c.pending_admin == *signer_info.key)?;
+
+    // Validate the timelock has elapsed.
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < config.pending_admin_at {
+        return Err(GaryError::AdminTimelocked.into());
+    }
+
+    // Update the admin.
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+    config.pending_admin_at = 0;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// AddBus creates the next bus account to spread mining over more write locks.
+pub fn process_add_bus(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, bus_info, config_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .is_config()?
+        .is_writable()?
+        .as_account_mut::<Config>(&gary_api::ID)?
+        .assert_mut(|c| c.admin == *signer_info.key)?;
+    let id = config.bus_count;
+    if id >= MAX_BUS_COUNT {
+        return Err(GaryError::InvalidParams.into());
+    }
+    bus_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[BUS, &[id as u8]], &gary_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Initialize the bus.
+    //
+    // The bus starts empty and is topped up by the next reset.
+    create_program_account::<Bus>(
+        bus_info,
+        system_program,
+        signer_info,
+        &gary_api::ID,
+        &[BUS, &[id as u8]],
+    )?;
+    let bus = bus_info.as_account_mut::<Bus>(&gary_api::ID)?;
+    bus.id = id;
+    bus.rewards = 0;
+    bus.theoretical_rewards = 0;
+
+    // Increment the bus count.
+    config.bus_count += 1;
+
+    Ok(())
+}
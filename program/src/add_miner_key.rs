@@ -0,0 +1,24 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// AddMinerKey authorizes an additional keypair to submit hashes for a proof.
+pub fn process_add_miner_key(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = AddMinerKey::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    proof_info.is_writable()?.as_proof()?.assert_err(
+        |p| p.authority == *signer_info.key,
+        ProgramError::MissingRequiredSignature,
+    )?;
+    let extension = proof_info.as_proof_extension_mut()?;
+
+    // Add the miner key.
+    extension.add_miner_key(args.miner)?;
+
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use gary_api::prelude::*;
+use gary_boost_api::state::{boost_pda, Boost};
 use steel::*;
 
 /// Claim distributes claimable GARY from the treasury to a miner.
+///
+/// Claims are rejected while [`PAUSE_CLAIM`] is set, unless they are signed by a boost. Boost
+/// deposits, withdrawals and claims all claim from the boost proof, so a paused claim would
+/// otherwise lock the stakers' funds.
 pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = Claim::try_from_bytes(data)?;
@@ -19,16 +24,31 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         .is_writable()?
         .as_token_account()?
         .assert(|t| t.mint() == MINT_ADDRESS)?;
-    let proof = proof_info
-        .as_account_mut::<Proof>(&gary_api::ID)?
-        .assert_mut_err(
-            |p| p.authority == *signer_info.key,
-            ProgramError::MissingRequiredSignature,
+    let proof = proof_info.as_proof_mut()?;
+    treasury_info
+        .is_treasury()?
+        .as_account::<Treasury>(&gary_api::ID)?
+        .assert_err(
+            |t| !t.is_paused(PAUSE_CLAIM) || is_boost(signer_info),
+            GaryError::Paused.into(),
         )?;
-    treasury_info.is_treasury()?;
     treasury_tokens_info.is_writable()?.is_treasury_tokens()?;
     token_program.is_program(&spl_token::ID)?;
 
+    // Validate the signer is allowed to claim.
+    //
+    // The authority may claim to any beneficiary. A claim delegate may only claim to the
+    // beneficiary the authority chose when setting the delegate.
+    if proof.authority != *signer_info.key {
+        let extension = proof_info.as_proof_extension()?;
+        if extension.claim_delegate == Pubkey::default()
+            || extension.claim_delegate != *signer_info.key
+            || extension.claim_beneficiary != *beneficiary_info.key
+        {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
     // Update miner balance.
     proof.balance = proof
         .balance
@@ -48,5 +68,88 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         &[TREASURY],
     )?;
 
+    // Log the claim.
+    ClaimEvent {
+        disc: GaryEvent::Claim as u64,
+        authority: proof.authority,
+        beneficiary: *beneficiary_info.key,
+        amount,
+        balance: proof.balance,
+        ts: clock.unix_timestamp,
+    }
+    .log();
+
     Ok(())
 }
+
+/// Returns true if the account is a boost. Only the boost program can sign for a boost, so this
+/// identifies claims made by it.
+fn is_boost(info: &AccountInfo<'_>) -> bool {
+    info.as_account::<Boost>(&gary_boost_api::ID)
+        .is_ok_and(|boost| boost_pda(boost.mint).0 == *info.key)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::size_of;
+
+    use gary_boost_api::state::{boost_pda, Boost};
+    use steel::*;
+
+    use super::is_boost;
+
+    #[test]
+    fn test_is_boost() {
+        let mint = Pubkey::new_unique();
+        let key = boost_pda(mint).0;
+        let mut lamports = 1_000_000;
+        let mut data = vec![0; 8 + size_of::<Boost>()];
+        data[0] = Boost::discriminator();
+        data[16..48].copy_from_slice(mint.as_ref());
+        let boost_info = AccountInfo::new(
+            &key,
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &gary_boost_api::ID,
+            false,
+            0,
+        );
+        assert!(is_boost(&boost_info));
+
+        // A miner signing for their own proof is not exempt from the claim pause.
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = vec![];
+        let miner_info = AccountInfo::new(
+            &key,
+            true,
+            true,
+            &mut lamports,
+            &mut data,
+            &system_program::ID,
+            false,
+            0,
+        );
+        assert!(!is_boost(&miner_info));
+
+        // Nor is a boost account at any other address than the boost of its mint.
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = vec![0; 8 + size_of::<Boost>()];
+        data[0] = Boost::discriminator();
+        data[16..48].copy_from_slice(mint.as_ref());
+        let other_info = AccountInfo::new(
+            &key,
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &gary_boost_api::ID,
+            false,
+            0,
+        );
+        assert!(!is_boost(&other_info));
+    }
+}
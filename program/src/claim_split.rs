@@ -0,0 +1,154 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// Claim split distributes claimable GARY from the treasury to several beneficiaries, by their
+/// basis point weights.
+pub fn process_claim_split(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = ClaimSplit::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+    let weights = args.weights.map(|w| u16::from_le_bytes(w) as u64);
+
+    // Load accounts.
+    //
+    // Only the authority may split a claim, since a claim delegate is bound to a single
+    // beneficiary.
+    let clock = Clock::get()?;
+    let [signer_info, proof_info, treasury_info, treasury_tokens_info, token_program, beneficiary_infos @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let proof = proof_info.as_proof_mut()?.assert_mut_err(
+        |p| p.authority == *signer_info.key,
+        ProgramError::MissingRequiredSignature,
+    )?;
+    treasury_info
+        .is_treasury()?
+        .as_account::<Treasury>(&gary_api::ID)?
+        .assert_err(|t| !t.is_paused(PAUSE_CLAIM), GaryError::Paused.into())?;
+    treasury_tokens_info.is_writable()?.is_treasury_tokens()?;
+    token_program.is_program(&spl_token::ID)?;
+    if beneficiary_infos.is_empty() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    for beneficiary_info in beneficiary_infos {
+        beneficiary_info
+            .is_writable()?
+            .as_token_account()?
+            .assert(|t| t.mint() == MINT_ADDRESS)?;
+    }
+
+    // Split the claim between the beneficiaries.
+    let amounts = calculate_splits(amount, &weights, beneficiary_infos.len())?;
+
+    // Update miner balance.
+    //
+    // The balance is debited once for the whole claim, so the transfers either all succeed or
+    // the claim fails.
+    proof.balance = proof
+        .balance
+        .checked_sub(amount)
+        .ok_or(GaryError::ClaimTooLarge)?;
+
+    // Update last claim timestamp.
+    proof.last_claim_at = clock.unix_timestamp;
+
+    // Transfer tokens from treasury to each beneficiary.
+    for (beneficiary_info, amount) in beneficiary_infos.iter().zip(amounts) {
+        transfer_signed(
+            treasury_info,
+            treasury_tokens_info,
+            beneficiary_info,
+            token_program,
+            amount,
+            &[TREASURY],
+        )?;
+
+        // Log the claim.
+        ClaimEvent {
+            disc: GaryEvent::Claim as u64,
+            authority: proof.authority,
+            beneficiary: *beneficiary_info.key,
+            amount,
+            balance: proof.balance,
+            ts: clock.unix_timestamp,
+        }
+        .log();
+    }
+
+    Ok(())
+}
+
+/// Splits an amount by basis point weights, paying the rounding remainder to the first
+/// beneficiary.
+///
+/// The weights of the beneficiaries must add up to 100%, and any weights past the number of
+/// beneficiaries must be zero.
+fn calculate_splits(
+    amount: u64,
+    weights: &[u64],
+    beneficiaries: usize,
+) -> Result<Vec<u64>, GaryError> {
+    if beneficiaries == 0 || beneficiaries > weights.len() {
+        return Err(GaryError::InvalidParams);
+    }
+    let (weights, unused) = weights.split_at(beneficiaries);
+    if unused.iter().any(|w| *w > 0) || weights.iter().sum::<u64>() != BPS_DENOMINATOR {
+        return Err(GaryError::InvalidParams);
+    }
+    let mut amounts: Vec<u64> = weights
+        .iter()
+        .map(|w| (amount as u128 * *w as u128 / BPS_DENOMINATOR as u128) as u64)
+        .collect();
+    amounts[0] += amount - amounts.iter().sum::<u64>();
+    Ok(amounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use gary_api::error::GaryError;
+
+    use super::calculate_splits;
+
+    #[test]
+    fn test_calculate_splits() {
+        let weights = [5_000, 3_000, 2_000, 0, 0, 0, 0, 0];
+        assert_eq!(
+            calculate_splits(1_000, &weights, 3),
+            Ok(vec![500, 300, 200])
+        );
+
+        // The rounding remainder is paid to the first beneficiary.
+        assert_eq!(calculate_splits(7, &weights, 3), Ok(vec![4, 2, 1]));
+
+        // A trailing beneficiary may have a zero weight.
+        assert_eq!(calculate_splits(7, &weights, 4), Ok(vec![4, 2, 1, 0]));
+    }
+
+    #[test]
+    fn test_calculate_splits_invalid() {
+        let weights = [5_000, 3_000, 2_000, 0, 0, 0, 0, 0];
+
+        // Weights must add up to 100% across the beneficiaries.
+        assert_eq!(
+            calculate_splits(1_000, &weights, 2),
+            Err(GaryError::InvalidParams)
+        );
+        assert_eq!(
+            calculate_splits(1_000, &[5_000, 6_000, 0, 0, 0, 0, 0, 0], 2),
+            Err(GaryError::InvalidParams)
+        );
+
+        // There must be between 1 and 8 beneficiaries.
+        assert_eq!(
+            calculate_splits(1_000, &weights, 0),
+            Err(GaryError::InvalidParams)
+        );
+        assert_eq!(
+            calculate_splits(1_000, &weights, 9),
+            Err(GaryError::InvalidParams)
+        );
+    }
+}
@@ -10,7 +10,7 @@ pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     signer_info.is_signer()?;
     proof_info
         .is_writable()?
-        .as_account::<Proof>(&gary_api::ID)?
+        .as_proof()?
         .assert_err(
             |p| p.authority == *signer_info.key,
             ProgramError::MissingRequiredSignature,
@@ -21,5 +21,14 @@ pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     // Return rent to signer.
     proof_info.close(signer_info)?;
 
+    // Log the close.
+    CloseEvent {
+        disc: GaryEvent::Close as u64,
+        authority: *signer_info.key,
+        proof: *proof_info.key,
+        ts: Clock::get()?.unix_timestamp,
+    }
+    .log();
+
     Ok(())
 }
@@ -6,7 +6,7 @@ use steel::*;
 /// Initialize sets up the GARY program to begin mining.
 pub fn process_initialize(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer_info, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, metadata_info, mint_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, metadata_program, rent_sysvar] =
+    let [signer_info, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, epoch_stats_info, metadata_info, mint_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, metadata_program, rent_sysvar] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -48,6 +48,10 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
         .is_empty()?
         .is_writable()?
         .has_seeds(&[CONFIG], &gary_api::ID)?;
+    epoch_stats_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[EPOCH_STATS], &gary_api::ID)?;
     metadata_info.is_empty()?.is_writable()?.has_seeds(
         &[
             METADATA,
@@ -102,6 +106,19 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], _data: &[u8]) -> Program
     config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
     config.last_reset_at = 0;
     config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
+    config.admin = *signer_info.key;
+    config.bus_count = BUS_COUNT as u64;
+    config.version = CONFIG_VERSION;
+    config.init_params();
+
+    // Initialize epoch stats.
+    create_program_account::<EpochStats>(
+        epoch_stats_info,
+        system_program,
+        signer_info,
+        &gary_api::ID,
+        &[EPOCH_STATS],
+    )?;
 
     // Initialize treasury.
     create_program_account::<Treasury>(
@@ -1,19 +1,50 @@
+mod accept_admin;
+mod add_bus;
+mod add_miner_key;
 mod claim;
+mod claim_split;
 mod close;
 mod initialize;
+mod migrate_bus;
+mod migrate_config;
+mod migrate_proof;
 mod mine;
 mod open;
+mod propose_admin;
+mod remove_miner_key;
 mod reset;
+mod set_claim_delegate;
+mod set_guardian;
+mod set_params;
+mod set_paused;
+mod transfer_proof;
 mod update;
 
+use accept_admin::*;
+use add_bus::*;
+use add_miner_key::*;
 use claim::*;
+use claim_split::*;
 use close::*;
 use initialize::*;
+use migrate_bus::*;
+use migrate_config::*;
+use migrate_proof::*;
 use mine::*;
 use open::*;
+use propose_admin::*;
+use remove_miner_key::*;
 use reset::*;
+use set_claim_delegate::*;
+use set_guardian::*;
+use set_params::*;
+use set_paused::*;
+use transfer_proof::*;
 use update::*;
 
+pub use mine::{pay_from_bus, BusPayout};
+pub use reset::{calculate_new_reward_rate, EpochOutcome, EpochProcessor};
+
 use gary_api::instruction::*;
 use steel::*;
 
@@ -31,7 +62,22 @@ pub fn process_instruction(
         GaryInstruction::Open => process_open(accounts, data)?,
         GaryInstruction::Reset => process_reset(accounts, data)?,
         GaryInstruction::Update => process_update(accounts, data)?,
+        GaryInstruction::MigrateProof => process_migrate_proof(accounts, data)?,
+        GaryInstruction::SetClaimDelegate => process_set_claim_delegate(accounts, data)?,
+        GaryInstruction::MigrateBus => process_migrate_bus(accounts, data)?,
+        GaryInstruction::TransferProof => process_transfer_proof(accounts, data)?,
+        GaryInstruction::AddMinerKey => process_add_miner_key(accounts, data)?,
+        GaryInstruction::RemoveMinerKey => process_remove_miner_key(accounts, data)?,
+        GaryInstruction::MineRelayed => process_mine_relayed(accounts, data)?,
+        GaryInstruction::ClaimSplit => process_claim_split(accounts, data)?,
         GaryInstruction::Initialize => process_initialize(accounts, data)?,
+        GaryInstruction::MigrateConfig => process_migrate_config(accounts, data)?,
+        GaryInstruction::SetParams => process_set_params(accounts, data)?,
+        GaryInstruction::ProposeAdmin => process_propose_admin(accounts, data)?,
+        GaryInstruction::AcceptAdmin => process_accept_admin(accounts, data)?,
+        GaryInstruction::AddBus => process_add_bus(accounts, data)?,
+        GaryInstruction::SetGuardian => process_set_guardian(accounts, data)?,
+        GaryInstruction::SetPaused => process_set_paused(accounts, data)?,
     }
 
     Ok(())
@@ -0,0 +1,31 @@
+use gary_api::prelude::*;
+use solana_program::rent::Rent;
+use steel::*;
+
+/// MigrateBus grows a legacy bus account to the layout with epoch stats counters.
+pub fn process_migrate_bus(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, bus_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    bus_info.is_writable()?.is_bus()?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Validate the bus account has not already been migrated.
+    if bus_info.data_len() == Bus::LEN {
+        return Ok(());
+    }
+
+    // Fund the bus account for its new size.
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(Bus::LEN);
+    let lamports = rent_exempt_lamports.saturating_sub(bus_info.lamports());
+    if lamports > 0 {
+        bus_info.collect(lamports, signer_info)?;
+    }
+
+    // Grow the bus account. The new counters start at zero.
+    bus_info.realloc(Bus::LEN, true)?;
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::mem::size_of;
+
+use gary_api::prelude::*;
+use solana_program::rent::Rent;
+use steel::*;
+
+/// MigrateConfig grows the config account to the current layout and sets the fields added by each
+/// layout version it is missing to their initial values. It also creates the epoch stats account,
+/// if it does not exist yet, and grows the treasury to hold a copy of the paused instructions.
+///
+/// Once the config has an admin, only the admin may migrate it. Until then, the initializer does.
+pub fn process_migrate_config(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, config_info, epoch_stats_info, treasury_info, system_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    config_info
+        .is_config()?
+        .is_writable()?
+        .is_type::<Config>(&gary_api::ID)?;
+    let authority = if config_info.data_len() < 8 + size_of::<Config>() {
+        INITIALIZER_ADDRESS
+    } else {
+        migration_authority(config_info.as_account::<Config>(&gary_api::ID)?)
+    };
+    signer_info.is_signer()?.has_address(&authority)?;
+    epoch_stats_info
+        .is_writable()?
+        .has_seeds(&[EPOCH_STATS], &gary_api::ID)?;
+    treasury_info.is_treasury()?.is_writable()?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Initialize epoch stats, if needed.
+    if epoch_stats_info.data_is_empty() {
+        create_program_account::<EpochStats>(
+            epoch_stats_info,
+            system_program,
+            signer_info,
+            &gary_api::ID,
+            &[EPOCH_STATS],
+        )?;
+    }
+
+    // Grow the config account, if needed.
+    let new_len = 8 + size_of::<Config>();
+    if config_info.data_len() < new_len {
+        // Fund the config account for its new size.
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(new_len);
+        let lamports = rent_exempt_lamports.saturating_sub(config_info.lamports());
+        if lamports > 0 {
+            config_info.collect(lamports, signer_info)?;
+        }
+        config_info.realloc(new_len, true)?;
+    }
+
+    // Grow the treasury account, if needed.
+    let new_len = 8 + size_of::<Treasury>();
+    if treasury_info.data_len() < new_len {
+        // Fund the treasury account for its new size.
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(new_len);
+        let lamports = rent_exempt_lamports.saturating_sub(treasury_info.lamports());
+        if lamports > 0 {
+            treasury_info.collect(lamports, signer_info)?;
+        }
+        treasury_info.realloc(new_len, true)?;
+    }
+
+    // Set the fields of each version the config is missing.
+    let config = config_info.as_account_mut::<Config>(&gary_api::ID)?;
+    config.migrate(*signer_info.key);
+
+    // Copy the paused instructions to the treasury.
+    let treasury = treasury_info.as_account_mut::<Treasury>(&gary_api::ID)?;
+    treasury.paused = config.paused;
+
+    Ok(())
+}
+
+/// Returns the authority allowed to migrate the config. The admin was added by layout version 2, so
+/// earlier configs are migrated by the initializer.
+fn migration_authority(config: &Config) -> Pubkey {
+    if config.version >= 2 {
+        config.admin
+    } else {
+        INITIALIZER_ADDRESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gary_api::{consts::INITIALIZER_ADDRESS, state::Config};
+    use steel::*;
+
+    use super::migration_authority;
+
+    #[test]
+    fn test_migration_authority() {
+        let admin = Pubkey::new_unique();
+        let mut config = Config::zeroed();
+        config.admin = admin;
+
+        // Configs without an admin are migrated by the initializer.
+        config.version = 1;
+        assert_eq!(migration_authority(&config), INITIALIZER_ADDRESS);
+
+        // Once the admin is set, the initializer may no longer migrate the config.
+        config.version = 2;
+        assert_eq!(migration_authority(&config), admin);
+        config.version = 6;
+        assert_eq!(migration_authority(&config), admin);
+    }
+}
@@ -0,0 +1,32 @@
+use gary_api::prelude::*;
+use solana_program::rent::Rent;
+use steel::*;
+
+/// MigrateProof grows a legacy proof account to the versioned layout.
+pub fn process_migrate_proof(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, proof_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    proof_info.is_writable()?.as_proof()?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Validate the proof account has not already been migrated.
+    if proof_info.data_len() == Proof::LEN {
+        return Ok(());
+    }
+
+    // Fund the proof account for its new size.
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(Proof::LEN);
+    let lamports = rent_exempt_lamports.saturating_sub(proof_info.lamports());
+    if lamports > 0 {
+        proof_info.collect(lamports, signer_info)?;
+    }
+
+    // Grow the proof account and initialize the extension.
+    proof_info.realloc(Proof::LEN, true)?;
+    ProofExtension::init_account_data(&mut proof_info.data.borrow_mut())?;
+
+    Ok(())
+}
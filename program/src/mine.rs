@@ -15,15 +15,44 @@ use solana_program::{
 use steel::*;
 
 /// Mine validates hashes and increments a miner's claimable balance.
+///
+/// While the referral window of the proof is open, its referrer proof must be passed after the
+/// boost accounts.
 pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = Mine::try_from_bytes(data)?;
+    mine(accounts, Solution::new(args.digest, args.nonce), None)
+}
+
+/// Mine relayed validates hashes like mine, but pays a share of the miner reward to the relayer
+/// which paid the transaction fees on the miner's behalf.
+///
+/// The relayer proof is passed after the boost accounts, followed by any referrer proof.
+pub fn process_mine_relayed(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = MineRelayed::try_from_bytes(data)?;
+    let fee_bps = u64::from_le_bytes(args.fee_bps);
+    if fee_bps > MAX_RELAYER_FEE_BPS {
+        return Err(GaryError::RelayerFeeTooHigh.into());
+    }
+    mine(
+        accounts,
+        Solution::new(args.digest, args.nonce),
+        Some(fee_bps),
+    )
+}
 
+/// Validates a solution and pays out its reward, with an optional relayer fee.
+fn mine(
+    accounts: &[AccountInfo],
+    solution: Solution,
+    relayer_fee_bps: Option<u64>,
+) -> ProgramResult {
     // Load accounts.
     let clock = Clock::get()?;
     let t: i64 = clock.unix_timestamp;
-    let (required_accounts, boost_accounts) = accounts.split_at(8);
-    let [signer_info, bus_info, config_info, proof_info, taxes_proof_info, fines_proof_info, instructions_sysvar, slot_hashes_sysvar] =
+    let (required_accounts, boost_accounts) = accounts.split_at(6);
+    let [signer_info, bus_info, config_info, proof_info, instructions_sysvar, slot_hashes_sysvar] =
         required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -33,42 +62,73 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let config = config_info
         .is_config()?
         .as_account::<Config>(&gary_api::ID)?
+        .assert_err(|c| !c.is_paused(PAUSE_MINE), GaryError::Paused.into())?
         .assert_err(
-            |c| t < c.last_reset_at + EPOCH_DURATION,
+            |c| t < c.last_reset_at + c.epoch_duration(),
             GaryError::NeedsReset.into(),
         )?;
-    let proof = proof_info
-        .as_account_mut::<Proof>(&gary_api::ID)?
-        .assert_mut_err(
-            |p| p.miner == *signer_info.key,
-            ProgramError::MissingRequiredSignature,
-        )?;
-    let taxes_proof = taxes_proof_info
-        .as_account_mut::<Proof>(&gary_api::ID)?
-        .assert_mut_err(
-            |p| p.miner == TAXES_ADDRESSES,
-            ProgramError::MissingRequiredSignature,
-        )?;
-    let fines_proof = fines_proof_info
-        .as_account_mut::<Proof>(&gary_api::ID)?
-        .assert_mut_err(
-            |p| p.miner == FINES_ADDRESSES,
-            ProgramError::MissingRequiredSignature,
-        )?;
-
+    let proof = proof_info.as_proof_mut()?;
+    if proof.miner != *signer_info.key && !is_miner_key(proof_info, signer_info.key) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
     instructions_sysvar.is_sysvar(&sysvar::instructions::ID)?;
     slot_hashes_sysvar.is_sysvar(&sysvar::slot_hashes::ID)?;
 
     // Load boost accounts.
-    let [boost_info, boost_proof_info, boost_config_info] = boost_accounts else {
+    let [boost_info, boost_proof_info, boost_config_info, remaining_accounts @ ..] = boost_accounts
+    else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     let boost = boost_info.as_account::<Boost>(&gary_boost_api::ID)?;
     let boost_config = boost_config_info.as_account::<BoostConfig>(&gary_boost_api::ID)?;
     let boost_proof = boost_proof_info
-        .as_account_mut::<Proof>(&gary_api::ID)?
+        .as_proof_mut()?
         .assert_mut(|p| p.authority == *boost_info.key)?;
 
+    // Load relayer accounts.
+    let (mut relayer, remaining_accounts) = match relayer_fee_bps {
+        Some(fee_bps) => {
+            let [relayer_proof_info, remaining_accounts @ ..] = remaining_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            if relayer_proof_info.key.eq(proof_info.key)
+                || relayer_proof_info.key.eq(boost_proof_info.key)
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            let relayer_proof = relayer_proof_info.as_proof_mut()?;
+            (
+                Some((relayer_proof_info, relayer_proof, fee_bps)),
+                remaining_accounts,
+            )
+        }
+        None => (None, remaining_accounts),
+    };
+
+    // Load referrer accounts.
+    //
+    // Proofs opened with a referrer pay it a share of taxes until the referral window has passed.
+    // Legacy proofs have no extension, so they have no referrer. If the referrer has since closed
+    // or transferred its proof, its share stays with the taxes, so a referrer cannot lock the
+    // proofs it referred out of mining.
+    let referrer_proof_info = match proof_info.as_proof_extension() {
+        Ok(extension)
+            if config.referral_percent > 0
+                && extension.is_referral_active(t, config.referral_window) =>
+        {
+            let Some(referrer_proof_info) = remaining_accounts.first() else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            if referrer_proof_info.key.ne(&extension.referrer)
+                || referrer_proof_info.key.eq(boost_proof_info.key)
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            Some(referrer_proof_info).filter(|info| is_live_proof(info))
+        }
+        _ => None,
+    };
+
     // Authenticate the proof account.
     //
     // Only one proof account can be used for any given transaction. All `mine` instructions
@@ -80,7 +140,7 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     // Miners are rate limited to approximately 1 hash per minute. If a miner attempts to submit
     // solutions more frequently than this, reject with an error.
     let t_target = proof.last_hash_at + ONE_MINUTE;
-    let t_spam = t_target - TOLERANCE;
+    let t_spam = t_target - config.tolerance;
     if t < t_spam {
         return Err(GaryError::Spam.into());
     }
@@ -89,7 +149,6 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     //
     // Here we use drillx to validate the provided solution is a valid hash of the challenge.
     // If invalid, we return an error.
-    let solution = Solution::new(args.digest, args.nonce);
     if !solution.is_valid(&proof.challenge) {
         return Err(GaryError::HashInvalid.into());
     }
@@ -113,7 +172,11 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         config.base_reward_rate * 2u64.checked_pow(normalized_difficulty).unwrap();
 
     // Zero out gross reward if boost is invalid.
-    if boost_config.current != *boost_info.key || t >= boost_config.ts + ROTATION_DURATION {
+    //
+    // If the boost rotation is overdue, the miner is instead paid the full reward without a staker
+    // boost. This way a late rotate crank does not zero out rewards across the network.
+    let boost_status = get_boost_status(boost_config, boost_info.key, t);
+    if boost_status == BoostStatus::Invalid {
         gross_reward = 0;
     }
 
@@ -126,7 +189,7 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     // The liveness penalty works by halving the reward amount for every minute a solution has been submitted late.
     // This ultimately drives the reward to zero given enough time (10-20 minutes).
     let mut gross_penalized_reward = gross_reward;
-    let t_liveness = t_target + TOLERANCE;
+    let t_liveness = t_target + config.tolerance;
     if t > t_liveness {
         // Halve the reward for every minute late.
         let secs_late = t.saturating_sub(t_target) as u64;
@@ -147,47 +210,72 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         }
     }
 
-    // Apply bus limit.
-    //
-    // Busses are limited to distributing the target emissions rate per epoch. The payout amount must be capped to whatever is
-    // left in the selected bus. This limits the maximum amount that will be paid out for any given hash to the target emissions rate.
-    let mut net_reward = gross_penalized_reward
-        .min(bus.rewards)
-        .min(config.target_emmissions_rate);
-    
-    // Update bus balances.
-    //
-    // We track the theoretical rewards that would have been paid out ignoring the bus limit, so the
-    // base reward rate will be updated to account for the real hashpower on the network.
-    bus.theoretical_rewards += gross_penalized_reward;
-    bus.rewards -= net_reward;
-    
-    // Apply taxes and fines.
-    let taxes_reward = net_reward * TAXES_PERCENT / FEE_PERCENT_DENOMINATOR;
-    taxes_proof.balance += taxes_reward;
-    let fines_reward = net_reward * FINES_PERCENT / FEE_PERCENT_DENOMINATOR;
-    fines_proof.balance += fines_reward;
-    net_reward -= taxes_reward + fines_reward;
+    // Pay the reward out of the bus.
+    let BusPayout {
+        net_reward,
+        referral_reward,
+    } = pay_from_bus(
+        config,
+        bus,
+        difficulty,
+        gross_penalized_reward,
+        referrer_proof_info.is_some(),
+    );
 
     // Split the net reward between the miner and stakers.
     //
     // The boost take rate is capped at 50% of the net reward. This protects miners from excessively
     // large boost incentives that would overly skew the distribution of rewards.
     let boost_bps = boost.bps.min(DENOMINATOR_BPS / 2);
-    let net_boost_reward = if t < boost.expires_at {
+    let net_boost_reward = if boost_status == BoostStatus::Active && t < boost.expires_at {
         (net_reward as u128 * boost_bps as u128 / DENOMINATOR_BPS as u128) as u64
     } else {
         0
     };
     let net_miner_reward = net_reward - net_boost_reward;
 
+    // Pay the relayer fee.
+    //
+    // A relayed hash pays the relayer its fee out of the miner reward, to reimburse the
+    // transaction fees it paid on the miner's behalf. The fee does not touch the staker boost.
+    let net_relayer_reward = relayer.as_ref().map_or(0, |(_, _, fee_bps)| {
+        calculate_relayer_fee(net_miner_reward, *fee_bps)
+    });
+    let net_miner_reward = net_miner_reward - net_relayer_reward;
+
     // Sanity check the rewards.
-    assert_eq!(net_reward, net_miner_reward + net_boost_reward);
+    assert_eq!(
+        net_reward,
+        net_miner_reward + net_boost_reward + net_relayer_reward
+    );
 
     // Update staker balances.
     boost_proof.balance += net_boost_reward;
     boost_proof.total_rewards += net_boost_reward;
 
+    // Update relayer balances.
+    if let Some((_, relayer_proof, _)) = relayer.as_mut() {
+        relayer_proof.balance += net_relayer_reward;
+        relayer_proof.total_rewards += net_relayer_reward;
+    }
+
+    // Update referrer balances.
+    //
+    // A referrer may also relay the hashes of the proofs it referred, in which case both are
+    // credited to the relayer proof already loaded.
+    if let Some(referrer_proof_info) = referrer_proof_info {
+        let referrer_proof = match relayer.as_mut() {
+            Some((relayer_proof_info, relayer_proof, _))
+                if relayer_proof_info.key.eq(referrer_proof_info.key) =>
+            {
+                &mut **relayer_proof
+            }
+            _ => referrer_proof_info.as_proof_mut()?,
+        };
+        referrer_proof.balance += referral_reward;
+        referrer_proof.total_rewards += referral_reward;
+    }
+
     // Update miner balances.
     proof.balance += net_miner_reward;
 
@@ -208,6 +296,30 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     proof.total_hashes += 1;
     proof.total_rewards += net_miner_reward;
 
+    // Log the referrer fee.
+    if let Some(referrer_proof_info) = referrer_proof_info {
+        ReferralEvent {
+            disc: GaryEvent::Referral as u64,
+            authority: proof.authority,
+            referrer: referrer_proof_info.as_proof()?.authority,
+            amount: referral_reward,
+            ts: t,
+        }
+        .log();
+    }
+
+    // Log the relayer fee.
+    if let Some((_, relayer_proof, _)) = relayer {
+        RelayEvent {
+            disc: GaryEvent::Relay as u64,
+            authority: proof.authority,
+            relayer: relayer_proof.authority,
+            amount: net_relayer_reward,
+            ts: t,
+        }
+        .log();
+    }
+
     // Log data.
     //
     // The boost rewards are scaled down before logging to account for penalties and bus limits.
@@ -227,6 +339,113 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     Ok(())
 }
 
+/// The share of a hash reward paid out of a bus.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BusPayout {
+    /// The reward left for the miner, stakers and relayer, after taxes and fines.
+    pub net_reward: u64,
+
+    /// The share of taxes paid to the referrer of the proof.
+    pub referral_reward: u64,
+}
+
+/// Pays a hash reward out of a bus, once the liveness penalty has been applied.
+///
+/// The simulator pays its hashes through here too, so its payouts stay in step with mine.
+pub fn pay_from_bus(
+    config: &Config,
+    bus: &mut Bus,
+    difficulty: u32,
+    gross_penalized_reward: u64,
+    referred: bool,
+) -> BusPayout {
+    // Apply bus limit.
+    //
+    // Busses are limited to distributing the target emissions rate per epoch. The payout amount must be capped to whatever is
+    // left in the selected bus. This limits the maximum amount that will be paid out for any given hash to the target emissions rate.
+    let mut net_reward = gross_penalized_reward
+        .min(bus.rewards)
+        .min(config.target_emmissions_rate);
+
+    // Update bus balances.
+    //
+    // We track the theoretical rewards that would have been paid out ignoring the bus limit, so the
+    // base reward rate will be updated to account for the real hashpower on the network.
+    bus.theoretical_rewards += gross_penalized_reward;
+    bus.rewards -= net_reward;
+    bus.record_hash(difficulty, net_reward);
+
+    // Apply taxes and fines.
+    //
+    // The fees accrue on the bus and are swept into the fee proofs at the next reset, so mine
+    // transactions only write-lock their bus and proof. A referred proof pays the referrer's
+    // share of taxes directly to the referrer proof instead.
+    let taxes_reward = net_reward * config.taxes_percent / FEE_PERCENT_DENOMINATOR;
+    let referral_reward = if referred {
+        calculate_referral_reward(taxes_reward, config.referral_percent)
+    } else {
+        0
+    };
+    bus.taxes += taxes_reward - referral_reward;
+    let fines_reward = net_reward * config.fines_percent / FEE_PERCENT_DENOMINATOR;
+    bus.fines += fines_reward;
+    net_reward -= taxes_reward + fines_reward;
+
+    BusPayout {
+        net_reward,
+        referral_reward,
+    }
+}
+
+/// Calculates the share of a miner reward paid to the relayer of a hash.
+fn calculate_relayer_fee(net_miner_reward: u64, fee_bps: u64) -> u64 {
+    (net_miner_reward as u128 * fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// Calculates the share of taxes paid to the referrer of a proof.
+fn calculate_referral_reward(taxes_reward: u64, referral_percent: u64) -> u64 {
+    (taxes_reward as u128 * referral_percent as u128 / FEE_PERCENT_DENOMINATOR as u128) as u64
+}
+
+/// Returns true if the account is an open proof, rather than one which was closed or
+/// transferred.
+fn is_live_proof(proof_info: &AccountInfo) -> bool {
+    proof_info.as_proof().is_ok()
+}
+
+/// Returns true if the key is one of the proof's additional miner keys.
+///
+/// Legacy proofs have no extension, so only their primary miner may submit hashes.
+fn is_miner_key(proof_info: &AccountInfo, key: &Pubkey) -> bool {
+    proof_info
+        .as_proof_extension()
+        .is_ok_and(|extension| extension.is_miner_key(key))
+}
+
+/// The status of the boost provided to mine.
+#[derive(Debug, PartialEq)]
+enum BoostStatus {
+    /// The boost is the current one and the rotation is fresh.
+    Active,
+
+    /// The boost is not the current one.
+    Invalid,
+
+    /// The rotation is overdue, so no boost applies.
+    Stale,
+}
+
+/// Get the status of a boost at the given time.
+fn get_boost_status(boost_config: &BoostConfig, boost: &Pubkey, t: i64) -> BoostStatus {
+    if t >= boost_config.ts + ROTATION_DURATION {
+        BoostStatus::Stale
+    } else if boost_config.current != *boost {
+        BoostStatus::Invalid
+    } else {
+        BoostStatus::Active
+    }
+}
+
 /// Authenticate the proof account.
 ///
 /// This process is necessary to prevent sybil attacks. If a user can pack multiple hashes into a single
@@ -280,3 +499,136 @@ fn parse_auth_address(data: &[u8]) -> Result<Option<Pubkey>, SanitizeError> {
     // Default return none
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use gary_boost_api::{consts::ROTATION_DURATION, state::Config as BoostConfig};
+    use steel::*;
+
+    use gary_api::state::{Bus, Config, Proof};
+
+    use super::{
+        calculate_referral_reward, calculate_relayer_fee, get_boost_status, is_live_proof,
+        pay_from_bus, BoostStatus, BusPayout,
+    };
+
+    #[test]
+    fn test_get_boost_status() {
+        let boost = Pubkey::new_unique();
+        let boost_config = BoostConfig {
+            current: boost,
+            ts: 1_000,
+            ..Zeroable::zeroed()
+        };
+        assert_eq!(
+            get_boost_status(&boost_config, &boost, 1_000),
+            BoostStatus::Active
+        );
+        assert_eq!(
+            get_boost_status(&boost_config, &Pubkey::new_unique(), 1_000),
+            BoostStatus::Invalid
+        );
+        assert_eq!(
+            get_boost_status(&boost_config, &boost, 1_000 + ROTATION_DURATION),
+            BoostStatus::Stale
+        );
+        assert_eq!(
+            get_boost_status(&boost_config, &Pubkey::new_unique(), 1_000 + ROTATION_DURATION),
+            BoostStatus::Stale
+        );
+    }
+
+    #[test]
+    fn test_calculate_relayer_fee() {
+        assert_eq!(calculate_relayer_fee(10_000, 0), 0);
+        assert_eq!(calculate_relayer_fee(10_000, 250), 250);
+        assert_eq!(calculate_relayer_fee(999, 5_000), 499);
+        assert_eq!(calculate_relayer_fee(u64::MAX, 10_000), u64::MAX);
+    }
+
+    #[test]
+    fn test_calculate_referral_reward() {
+        assert_eq!(calculate_referral_reward(1_000, 0), 0);
+        assert_eq!(calculate_referral_reward(1_000, 200), 200);
+        assert_eq!(calculate_referral_reward(999, 500), 499);
+        assert_eq!(calculate_referral_reward(u64::MAX, 1_000), u64::MAX);
+    }
+
+    #[test]
+    fn test_pay_from_bus() {
+        let config = Config {
+            target_emmissions_rate: 5_000,
+            taxes_percent: 100,
+            fines_percent: 50,
+            referral_percent: 200,
+            ..Zeroable::zeroed()
+        };
+        let mut bus = Bus {
+            rewards: 4_000,
+            ..Zeroable::zeroed()
+        };
+
+        // The reward is capped to the bus balance, and the fees accrue on the bus.
+        let payout = pay_from_bus(&config, &mut bus, 10, 6_000, false);
+        assert_eq!(
+            payout,
+            BusPayout {
+                net_reward: 3_400,
+                referral_reward: 0,
+            }
+        );
+        assert_eq!(bus.rewards, 0);
+        assert_eq!(bus.theoretical_rewards, 6_000);
+        assert_eq!(bus.paid_rewards, 4_000);
+        assert_eq!(bus.taxes, 400);
+        assert_eq!(bus.fines, 200);
+
+        // A referred proof carves the referrer's share out of the taxes.
+        bus.rewards = 10_000;
+        let payout = pay_from_bus(&config, &mut bus, 10, 6_000, true);
+        assert_eq!(
+            payout,
+            BusPayout {
+                net_reward: 4_250,
+                referral_reward: 100,
+            }
+        );
+        assert_eq!(bus.rewards, 5_000);
+        assert_eq!(bus.taxes, 800);
+        assert_eq!(bus.fines, 450);
+    }
+
+    #[test]
+    fn test_is_live_proof() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = vec![0; Proof::LEN];
+        data[0] = Proof::discriminator();
+        let proof_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &gary_api::ID,
+            false,
+            0,
+        );
+        assert!(is_live_proof(&proof_info));
+
+        // A closed referrer proof is returned to the system program with no data.
+        let mut lamports = 0;
+        let mut data = vec![];
+        let closed_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &system_program::ID,
+            false,
+            0,
+        );
+        assert!(!is_live_proof(&closed_info));
+    }
+}
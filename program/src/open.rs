@@ -5,9 +5,12 @@ use solana_program::{keccak::hashv, slot_hashes::SlotHash};
 use steel::*;
 
 /// Open creates a new proof account to track a miner's state.
+///
+/// An optional referrer proof may be passed last, which is paid a share of the taxes on the new
+/// proof's hashes for the referral window.
 pub fn process_open(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer_info, miner_info, payer_info, proof_info, system_program, slot_hashes_info] =
+    let [signer_info, miner_info, payer_info, proof_info, system_program, slot_hashes_info, referrer_accounts @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -20,17 +23,30 @@ pub fn process_open(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult
         .has_seeds(&[PROOF, signer_info.key.as_ref()], &gary_api::ID)?;
     system_program.is_program(&system_program::ID)?;
     slot_hashes_info.is_sysvar(&sysvar::slot_hashes::ID)?;
+    let referrer = match referrer_accounts.first() {
+        Some(referrer_proof_info) => {
+            referrer_proof_info.as_proof()?;
+            *referrer_proof_info.key
+        }
+        None => Pubkey::default(),
+    };
 
     // Initialize proof.
-    create_program_account::<Proof>(
+    allocate_account(
         proof_info,
         system_program,
         payer_info,
+        Proof::LEN,
         &gary_api::ID,
         &[PROOF, signer_info.key.as_ref()],
     )?;
+    proof_info.data.borrow_mut()[0] = Proof::discriminator();
+    ProofExtension::init_account_data(&mut proof_info.data.borrow_mut())?;
     let clock = Clock::get()?;
-    let proof = proof_info.as_account_mut::<Proof>(&gary_api::ID)?;
+    let extension = proof_info.as_proof_extension_mut()?;
+    extension.referrer = referrer;
+    extension.opened_at = clock.unix_timestamp;
+    let proof = proof_info.as_proof_mut()?;
     proof.authority = *signer_info.key;
     proof.balance = 0;
     proof.challenge = hashv(&[
@@ -44,5 +60,15 @@ pub fn process_open(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult
     proof.total_hashes = 0;
     proof.total_rewards = 0;
 
+    // Log the open.
+    OpenEvent {
+        disc: GaryEvent::Open as u64,
+        authority: *signer_info.key,
+        miner: *miner_info.key,
+        proof: *proof_info.key,
+        ts: clock.unix_timestamp,
+    }
+    .log();
+
     Ok(())
 }
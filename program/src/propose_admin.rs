@@ -0,0 +1,32 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// ProposeAdmin nominates a new admin authority, which must accept before taking effect.
+pub fn process_propose_admin(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = ProposeAdmin::try_from_bytes(data)?;
+    let timelock = i64::from_le_bytes(args.timelock);
+    if timelock < 0 {
+        return Err(GaryError::InvalidParams.into());
+    }
+
+    // Load accounts.
+    let [signer_info, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .is_config()?
+        .is_writable()?
+        .as_account_mut::<Config>(&gary_api::ID)?
+        .assert_mut(|c| c.admin == *signer_info.key)?;
+
+    // Propose the new admin.
+    //
+    // Proposing the default pubkey cancels any pending transfer.
+    let clock = Clock::get()?;
+    config.pending_admin = args.new_admin;
+    config.pending_admin_at = clock.unix_timestamp.saturating_add(timelock);
+
+    Ok(())
+}
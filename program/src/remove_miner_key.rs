@@ -0,0 +1,24 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// RemoveMinerKey revokes an additional keypair's permission to submit hashes for a proof.
+pub fn process_remove_miner_key(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = RemoveMinerKey::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    proof_info.is_writable()?.as_proof()?.assert_err(
+        |p| p.authority == *signer_info.key,
+        ProgramError::MissingRequiredSignature,
+    )?;
+    let extension = proof_info.as_proof_extension_mut()?;
+
+    // Remove the miner key.
+    extension.remove_miner_key(&args.miner);
+
+    Ok(())
+}
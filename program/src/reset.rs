@@ -1,42 +1,31 @@
 use gary_api::prelude::*;
 use steel::*;
 
+/// The highest min difficulty the EMA retarget mode will set.
+const MAX_RETARGET_DIFFICULTY: u64 = 64;
+
 /// Reset tops up the bus balances and updates the emissions and reward rates.
+///
+/// Every bus must be passed in order of id. If the signer's proof account exists, it is credited
+/// the configured crank reward, minted alongside the epoch's bus top ups. The taxes and fines
+/// accrued on the busses are swept into the proofs of the configured fee addresses. If a fee proof is missing or belongs to another
+/// authority, its fees are left on the first bus to be swept in a later epoch.
 pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer_info, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
+    let [signer_info, config_info, epoch_stats_info, mint_info, treasury_info, treasury_tokens_info, token_program, proof_info, taxes_proof_info, fines_proof_info, bus_infos @ ..] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    let bus_0 = bus_0_info
-        .as_account_mut::<Bus>(&gary_api::ID)?
-        .assert_mut(|b| b.id == 0)?;
-    let bus_1 = bus_1_info
-        .as_account_mut::<Bus>(&gary_api::ID)?
-        .assert_mut(|b| b.id == 1)?;
-    let bus_2 = bus_2_info
-        .as_account_mut::<Bus>(&gary_api::ID)?
-        .assert_mut(|b| b.id == 2)?;
-    let bus_3 = bus_3_info
-        .as_account_mut::<Bus>(&gary_api::ID)?
-        .assert_mut(|b| b.id == 3)?;
-    let bus_4 = bus_4_info
-        .as_account_mut::<Bus>(&gary_api::ID)?
-        .assert_mut(|b| b.id == 4)?;
-    let bus_5 = bus_5_info
-        .as_account_mut::<Bus>(&gary_api::ID)?
-        .assert_mut(|b| b.id == 5)?;
-    let bus_6 = bus_6_info
-        .as_account_mut::<Bus>(&gary_api::ID)?
-        .assert_mut(|b| b.id == 6)?;
-    let bus_7 = bus_7_info
-        .as_account_mut::<Bus>(&gary_api::ID)?
-        .assert_mut(|b| b.id == 7)?;
     let config = config_info
         .is_config()?
-        .as_account_mut::<Config>(&gary_api::ID)?;
+        .as_account_mut::<Config>(&gary_api::ID)?
+        .assert_mut_err(|c| !c.is_paused(PAUSE_RESET), GaryError::Paused.into())?;
+    let epoch_stats = epoch_stats_info
+        .is_epoch_stats()?
+        .is_writable()?
+        .as_account_mut::<EpochStats>(&gary_api::ID)?;
     let mint = mint_info
         .has_address(&MINT_ADDRESS)?
         .is_writable()?
@@ -44,107 +33,383 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
     treasury_info.is_treasury()?.is_writable()?;
     treasury_tokens_info.is_treasury_tokens()?.is_writable()?;
     token_program.is_program(&spl_token::ID)?;
+    let proof = if proof_info.data_is_empty() {
+        None
+    } else {
+        Some(
+            proof_info
+                .is_writable()?
+                .as_proof_mut()?
+                .assert_mut_err(
+                    |p| p.authority == *signer_info.key || p.miner == *signer_info.key,
+                    ProgramError::MissingRequiredSignature,
+                )?,
+        )
+    };
+    let sweep_taxes = is_fee_proof(taxes_proof_info, &config.taxes_address);
+    let sweep_fines = is_fee_proof(fines_proof_info, &config.fines_address);
+    if bus_infos.len() as u64 != config.bus_count {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let busses = bus_infos
+        .iter()
+        .enumerate()
+        .map(|(i, bus_info)| {
+            bus_info
+                .as_account_mut::<Bus>(&gary_api::ID)?
+                .assert_mut(|b| b.id == i as u64)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
     // Validate enough time has passed since the last reset.
     let clock = Clock::get()?;
     if config
         .last_reset_at
-        .saturating_add(EPOCH_DURATION as i64)
+        .saturating_add(config.epoch_duration())
         .gt(&clock.unix_timestamp)
     {
         return Ok(());
     }
 
-    // Process epoch.
-    let busses = [bus_0, bus_1, bus_2, bus_3, bus_4, bus_5, bus_6, bus_7];
-    let amount_to_mint = config.process_epoch(busses, &clock, &mint)?;
+    // Process epoch, paying the crank reward if the signer has a proof.
+    let requested_reset_reward = if proof.is_some() {
+        config.reset_reward
+    } else {
+        0
+    };
+    let EpochOutcome {
+        amount_to_mint,
+        mut taxes,
+        mut fines,
+        reset_reward,
+    } = config.process_epoch(busses, epoch_stats, &clock, &mint, requested_reset_reward)?;
+
+    // Credit the crank reward to the signer's proof.
+    if let Some(proof) = proof {
+        proof.balance = proof.balance.saturating_add(reset_reward);
+        proof.total_rewards = proof.total_rewards.saturating_add(reset_reward);
+    }
+
+    // Sweep the taxes and fines into the fee proofs.
+    //
+    // The proofs are loaded one at a time, since the fee addresses may be the same. Fees which
+    // cannot be swept are returned to the first bus, so they are collected again next epoch.
+    if sweep_taxes {
+        taxes_proof_info.as_proof_mut()?.balance += taxes;
+    } else if let Some(bus_info) = bus_infos.first() {
+        bus_info.as_account_mut::<Bus>(&gary_api::ID)?.taxes += taxes;
+        taxes = 0;
+    }
+    if sweep_fines {
+        fines_proof_info.as_proof_mut()?.balance += fines;
+    } else if let Some(bus_info) = bus_infos.first() {
+        bus_info.as_account_mut::<Bus>(&gary_api::ID)?.fines += fines;
+        fines = 0;
+    }
 
     // Fund the treasury token account.
-    mint_to_signed(
-        mint_info,
-        treasury_tokens_info,
-        treasury_info,
-        token_program,
-        amount_to_mint,
-        &[TREASURY],
-    )?;
+    if amount_to_mint > 0 {
+        mint_to_signed(
+            mint_info,
+            treasury_tokens_info,
+            treasury_info,
+            token_program,
+            amount_to_mint,
+            &[TREASURY],
+        )?;
+    }
+
+    // Log the new epoch parameters.
+    ResetEvent {
+        disc: GaryEvent::Reset as u64,
+        base_reward_rate: config.base_reward_rate,
+        min_difficulty: config.min_difficulty,
+        target_emmissions_rate: config.target_emmissions_rate,
+        amount_minted: amount_to_mint,
+        reset_reward,
+        taxes,
+        fines,
+        supply: mint.supply().saturating_add(amount_to_mint),
+        ts: clock.unix_timestamp,
+    }
+    .log();
 
     Ok(())
 }
 
-trait EpochProcessor {
-    fn process_epoch(
+/// Returns true if the account is a writable, open proof of the given fee authority.
+fn is_fee_proof(proof_info: &AccountInfo, authority: &Pubkey) -> bool {
+    proof_info.is_writable
+        && proof_info
+            .as_proof()
+            .is_ok_and(|proof| proof.authority == *authority)
+}
+
+/// The result of processing an epoch.
+#[derive(Debug, Default, PartialEq)]
+pub struct EpochOutcome {
+    /// The amount to mint for bus top ups and the crank reward.
+    pub amount_to_mint: u64,
+
+    /// The taxes to credit to the taxes proof.
+    pub taxes: u64,
+
+    /// The fines to credit to the fines proof.
+    pub fines: u64,
+
+    /// The crank reward to credit to the proof of the reset signer.
+    pub reset_reward: u64,
+}
+
+/// EpochProcessor closes out an epoch, topping up the busses and updating the reward rate and
+/// difficulty for the next one.
+///
+/// The crank reward is minted on top of the bus top ups while emissions last, capped at the max
+/// supply, and paid out of the reward pool once they have ended. Either way it never comes out of
+/// a bus, so it is paid in full however depleted the busses are.
+pub trait EpochProcessor {
+    fn process_epoch<'a>(
         &mut self,
-        busses: [&mut Bus; 8],
+        busses: impl IntoIterator<Item = &'a mut Bus>,
+        epoch_stats: &mut EpochStats,
         clock: &Clock,
         mint: &Mint,
-    ) -> Result<u64, ProgramError>;
+        reset_reward: u64,
+    ) -> Result<EpochOutcome, ProgramError>;
 }
 
 impl EpochProcessor for Config {
-    fn process_epoch(
+    fn process_epoch<'a>(
         &mut self,
-        busses: [&mut Bus; 8],
+        busses: impl IntoIterator<Item = &'a mut Bus>,
+        epoch_stats: &mut EpochStats,
         clock: &Clock,
         mint: &Mint,
-    ) -> Result<u64, ProgramError> {
-        // Max supply check.
-        if mint.supply() >= MAX_SUPPLY {
-            return Err(GaryError::MaxSupply.into());
-        }
-
+        reset_reward: u64,
+    ) -> Result<EpochOutcome, ProgramError> {
         // Update timestamp.
         self.last_reset_at = clock.unix_timestamp;
 
-        // Adjust emissions curve based on current supply.
-        self.target_emmissions_rate = get_target_emissions_rate(mint.supply());
-
-        // Calculate target rewards to distribute in coming epoch (emissions rate multiplied by epoch duration).
-        let target_epoch_rewards = self.target_emmissions_rate * EPOCH_MINUTES as u64;
+        // Start the summary of the last epoch.
+        let mut summary = EpochSummary {
+            ended_at: clock.unix_timestamp,
+            min_difficulty: self.min_difficulty,
+            base_reward_rate: self.base_reward_rate,
+            ..Zeroable::zeroed()
+        };
 
-        // Reset bus counters and calculate theoretical rewards mined in the last epoch.
-        let mut amount_to_mint = 0u64;
-        let mut remaining_supply = MAX_SUPPLY.saturating_sub(mint.supply());
+        // Reset bus counters, collect the accrued fees, and calculate theoretical rewards mined in the last epoch.
+        let mut busses: Vec<&mut Bus> = busses.into_iter().collect();
+        let mut outcome = EpochOutcome::default();
         let mut theoretical_epoch_rewards = 0u64;
-        for bus in busses {
+        for bus in busses.iter_mut() {
             // Reset theoretical rewards.
             theoretical_epoch_rewards += bus.theoretical_rewards;
             bus.theoretical_rewards = 0;
 
-            // Reset bus rewards.
+            // Collect and reset epoch stats.
+            summary.hashes += bus.hashes;
+            summary.paid_rewards += bus.paid_rewards;
+            for (total, count) in summary
+                .difficulty_histogram
+                .iter_mut()
+                .zip(bus.difficulty_histogram.iter())
+            {
+                *total += count;
+            }
+            bus.hashes = 0;
+            bus.paid_rewards = 0;
+            bus.difficulty_histogram = [0; DIFFICULTY_HISTOGRAM_LEN];
+
+            // Collect taxes and fines.
+            outcome.taxes += bus.taxes;
+            outcome.fines += bus.fines;
+            bus.taxes = 0;
+            bus.fines = 0;
+        }
+
+        // Once the emissions schedule is exhausted, nothing more is minted. The busses are instead
+        // funded from the reward pool, which recycles a share of the taxes and fines.
+        let emissions_rate = emissions_at_supply(mint.supply());
+        let terminal = emissions_rate == 0;
+        if terminal {
+            let recycled_taxes = outcome.taxes * self.recycle_percent / FEE_PERCENT_DENOMINATOR;
+            let recycled_fines = outcome.fines * self.recycle_percent / FEE_PERCENT_DENOMINATOR;
+            outcome.taxes -= recycled_taxes;
+            outcome.fines -= recycled_fines;
+            self.reward_pool += recycled_taxes + recycled_fines;
+
+            // Pay the crank reward out of the reward pool.
+            outcome.reset_reward = reset_reward.min(self.reward_pool);
+            self.reward_pool -= outcome.reset_reward;
+        }
+
+        // Adjust emissions curve based on current supply, or spread the reward pool over the coming epoch.
+        self.target_emmissions_rate = if terminal {
+            self.reward_pool
+                .saturating_div(busses.len() as u64)
+                .saturating_div(self.epoch_minutes as u64)
+        } else {
+            emissions_rate
+        };
+
+        // Calculate target rewards to distribute in coming epoch (emissions rate multiplied by epoch duration).
+        let target_epoch_rewards = self.target_emmissions_rate * self.epoch_minutes as u64;
+
+        // Top up bus rewards.
+        let mut remaining_rewards = if terminal {
+            self.reward_pool
+        } else {
+            MAX_SUPPLY.saturating_sub(mint.supply())
+        };
+        for bus in busses {
             let topup_amount = target_epoch_rewards
                 .saturating_sub(bus.rewards)
-                .min(remaining_supply);
-            remaining_supply -= topup_amount;
-            amount_to_mint += topup_amount;
+                .min(remaining_rewards);
+            remaining_rewards -= topup_amount;
             bus.rewards += topup_amount;
+            if terminal {
+                self.reward_pool -= topup_amount;
+            } else {
+                outcome.amount_to_mint += topup_amount;
+            }
         }
 
-        // Update base reward rate for next epoch.
-        self.base_reward_rate = calculate_new_reward_rate(
-            self.base_reward_rate,
-            theoretical_epoch_rewards,
-            target_epoch_rewards,
-        );
+        // Mint the crank reward on top of the top ups, without exceeding the max supply.
+        if !terminal {
+            outcome.reset_reward = reset_reward.min(remaining_rewards);
+            outcome.amount_to_mint += outcome.reset_reward;
+        }
+
+        // Record the last epoch.
+        summary.theoretical_rewards = theoretical_epoch_rewards;
+        summary.amount_minted = outcome.amount_to_mint;
+        epoch_stats.push(summary);
 
-        // If base reward rate is too low, increment min difficulty by 1 and double base reward rate.
-        if self.base_reward_rate < BASE_REWARD_RATE_MIN_THRESHOLD {
-            self.min_difficulty += 1;
-            self.base_reward_rate *= 2;
+        // Leave the reward rate and difficulty unchanged if there is nothing to distribute.
+        if target_epoch_rewards == 0 {
+            return Ok(outcome);
         }
 
-        // If base reward rate is too high, decrement min difficulty by 1 and halve base reward rate.
-        if self.base_reward_rate > BASE_REWARD_RATE_MAX_THRESHOLD {
-            while self.base_reward_rate > BASE_REWARD_RATE_MAX_THRESHOLD {
-                self.base_reward_rate >>= 1;
-            }
-            if self.min_difficulty > 1 {
-                self.min_difficulty -= 1;
+        // Update base reward rate and min difficulty for next epoch.
+        match self.retarget_mode() {
+            RetargetMode::Threshold => {
+                retarget_threshold(self, theoretical_epoch_rewards, target_epoch_rewards)
             }
+            RetargetMode::Ema => retarget_ema(self, theoretical_epoch_rewards, target_epoch_rewards),
         }
 
-        Ok(amount_to_mint)
+        Ok(outcome)
+    }
+}
+
+/// Retargets by scaling the base reward rate by the rewards of the last epoch, and stepping the
+/// min difficulty by one whenever the rate crosses a threshold.
+fn retarget_threshold(config: &mut Config, epoch_rewards: u64, target_epoch_rewards: u64) {
+    // Update base reward rate for next epoch.
+    config.base_reward_rate = calculate_new_reward_rate(
+        config.base_reward_rate,
+        epoch_rewards,
+        target_epoch_rewards,
+        config.smoothing_factor,
+    );
+
+    // If base reward rate is too low, increment min difficulty by 1 and double base reward rate.
+    if config.base_reward_rate < config.base_reward_rate_min_threshold {
+        config.min_difficulty += 1;
+        config.base_reward_rate *= 2;
+    }
+
+    // If base reward rate is too high, decrement min difficulty by 1 and halve base reward rate.
+    if config.base_reward_rate > config.base_reward_rate_max_threshold {
+        while config.base_reward_rate > config.base_reward_rate_max_threshold {
+            config.base_reward_rate >>= 1;
+        }
+        if config.min_difficulty > 1 {
+            config.min_difficulty -= 1;
+        }
+    }
+}
+
+/// Retargets from a moving average of the work done per epoch.
+///
+/// Hashes pay out in proportion to their work, so paying the target over the average work sets a
+/// fractional difficulty. The base reward rate carries the fraction, and the min difficulty only
+/// moves when the rate leaves its thresholds. Averaging over several epochs keeps a single noisy
+/// epoch from swinging payouts the way the threshold mode does.
+fn retarget_ema(config: &mut Config, epoch_rewards: u64, target_epoch_rewards: u64) {
+    // Leave the reward rate unchanged if nothing was mined.
+    if epoch_rewards == 0 {
+        return;
+    }
+
+    // Update the moving average of work.
+    let epoch_work =
+        calculate_epoch_work(epoch_rewards, config.base_reward_rate, config.min_difficulty);
+    config.ema_epoch_work = calculate_ema(config.ema_epoch_work, epoch_work, config.ema_window);
+
+    // Pay the target epoch rewards over the average work.
+    (config.base_reward_rate, config.min_difficulty) = calculate_ema_retarget(
+        config.ema_epoch_work,
+        target_epoch_rewards,
+        config.min_difficulty,
+        config.base_reward_rate_min_threshold,
+        config.base_reward_rate_max_threshold,
+    );
+}
+
+/// Calculates the work behind theoretical rewards paid at the given base reward rate and min
+/// difficulty. This is what the rewards would have been at a base reward rate of 1 and a min
+/// difficulty of 0, so it measures hashpower independently of the parameters it was mined at.
+fn calculate_epoch_work(epoch_rewards: u64, base_reward_rate: u64, min_difficulty: u64) -> u64 {
+    (epoch_rewards as u128)
+        .saturating_mul(2u128.saturating_pow(min_difficulty as u32))
+        .saturating_div(base_reward_rate.max(1) as u128)
+        .min(u64::MAX as u128) as u64
+}
+
+/// Moves an exponential moving average over `window` epochs towards a new value. An empty average
+/// is seeded with the value.
+fn calculate_ema(ema: u64, value: u64, window: u64) -> u64 {
+    if ema == 0 {
+        return value;
+    }
+    let delta = (value as i128 - ema as i128) / window.max(1) as i128;
+    (ema as i128 + delta) as u64
+}
+
+/// Calculates the base reward rate and min difficulty which pay out the target epoch rewards for
+/// the given work.
+///
+/// The min difficulty is kept while the base reward rate stays within the thresholds. Otherwise it
+/// moves by the fewest steps which bring the rate back within them.
+fn calculate_ema_retarget(
+    work: u64,
+    target_epoch_rewards: u64,
+    min_difficulty: u64,
+    min_threshold: u64,
+    max_threshold: u64,
+) -> (u64, u64) {
+    let rate_at = |difficulty: u64| {
+        (target_epoch_rewards as u128)
+            .saturating_mul(2u128.saturating_pow(difficulty as u32))
+            .saturating_div(work.max(1) as u128)
+            .min(u64::MAX as u128) as u64
+    };
+    let mut difficulty = min_difficulty.max(1);
+    while difficulty < MAX_RETARGET_DIFFICULTY && rate_at(difficulty) < min_threshold {
+        difficulty += 1;
+    }
+    while difficulty > 1 && rate_at(difficulty) > max_threshold {
+        difficulty -= 1;
     }
+
+    // Prevent reward rate from dropping below 1 or exceeding the thresholds and target rewards.
+    let rate = rate_at(difficulty)
+        .min(max_threshold)
+        .min(target_epoch_rewards)
+        .max(1);
+    (rate, difficulty)
 }
 
 /// This function calculates what the new reward rate should be based on how many total rewards
@@ -153,13 +418,14 @@ impl EpochProcessor for Config {
 ///
 /// new_rate = current_rate * (target_rewards / actual_rewards)
 ///
-/// The new rate is then smoothed by a constant factor to avoid large fluctuations. In Gary's case,
-/// the epochs are short (60 seconds) so a smoothing factor of 2 has been chosen. That is, the reward rate
+/// The new rate is then smoothed by the configured factor to avoid large fluctuations. In Gary's case,
+/// the epochs are short (15 minutes) so a smoothing factor of 2 is the default. That is, the reward rate
 /// can at most double or halve from one epoch to the next.
-pub(crate) fn calculate_new_reward_rate(
+pub fn calculate_new_reward_rate(
     current_rate: u64,
     epoch_rewards: u64,
     target_epoch_rewards: u64,
+    smoothing_factor: u64,
 ) -> u64 {
     // Avoid division by zero. Leave the reward rate unchanged, if detected.
     if epoch_rewards.eq(&0) {
@@ -172,73 +438,114 @@ pub(crate) fn calculate_new_reward_rate(
         .saturating_div(epoch_rewards as u128) as u64;
 
     // Smooth reward rate so it cannot change by more than a constant factor from one epoch to the next.
-    let new_rate_min = current_rate.saturating_div(SMOOTHING_FACTOR);
-    let new_rate_max = current_rate.saturating_mul(SMOOTHING_FACTOR);
+    let new_rate_min = current_rate.saturating_div(smoothing_factor);
+    let new_rate_max = current_rate.saturating_mul(smoothing_factor);
     let new_rate_smoothed = new_rate.min(new_rate_max).max(new_rate_min);
 
     // Prevent reward rate from dropping below 1 or exceeding BUS_EPOCH_REWARDS and return.
     new_rate_smoothed.max(1).min(target_epoch_rewards)
 }
 
-/// This function calculates the target emissions rate (GARY / min) based on the current supply.
-/// It is designed to reduce emissions by 50% approximately every 12 months, max to 40 years.
-pub(crate) fn get_target_emissions_rate(current_supply: u64) -> u64 {
-    if current_supply >= MAX_SUPPLY {
-        return 0
-    }
-
-    let mut rate = 1f64;
-    let mut supply_threshold = 525_600;
-
-    for _ in 0..40 {
-        if current_supply < supply_threshold * TARGET_MINUTE_REWARDS {
-            return (rate * TARGET_MINUTE_REWARDS as f64) as u64;
-        }
-        rate *= 0.5;
-        supply_threshold += (525_600f64 * rate) as u64;
-    }
-
-    (rate * TARGET_MINUTE_REWARDS as f64) as u64
-}
-
 #[cfg(test)]
 mod tests {
     use rand::{distributions::Uniform, Rng};
     use solana_program::program_option::COption;
-    use steel::{Clock, Mint};
+    use steel::{AccountInfo, Clock, Discriminator, Mint, Pubkey, Zeroable};
 
-    use crate::reset::get_target_emissions_rate;
+    use crate::reset::{
+        calculate_ema, calculate_ema_retarget, calculate_epoch_work, is_fee_proof,
+        EpochOutcome,
+    };
     use crate::{calculate_new_reward_rate, reset::EpochProcessor};
-    use gary_api::consts::{TARGET_EPOCH_REWARDS, TARGET_MINUTE_REWARDS};
+    use gary_api::consts::{FEE_PERCENT_DENOMINATOR, TARGET_EPOCH_REWARDS, TARGET_MINUTE_REWARDS};
     use gary_api::{
         consts::{
-            BASE_REWARD_RATE_MIN_THRESHOLD, BUS_COUNT, EPOCH_MINUTES, SMOOTHING_FACTOR,
-            TOKEN_DECIMALS,
+            BUS_COUNT, DIFFICULTY_HISTOGRAM_LEN, INITIAL_BASE_REWARD_RATE_MIN_THRESHOLD, INITIAL_EPOCH_MINUTES,
+            INITIAL_RESET_REWARD, INITIAL_SMOOTHING_FACTOR, MAX_SUPPLY, TOKEN_DECIMALS,
         },
-        state::{Bus, Config},
+        emissions::{emissions_at_supply, EMISSIONS_HALVINGS, EMISSIONS_SCHEDULE, TAIL_EMISSIONS_RATE},
+        state::{Bus, Config, EpochStats, Proof, RetargetMode},
     };
 
     const FUZZ_SIZE: u64 = 10_000;
     const MAX_EPOCH_REWARDS: u64 = TARGET_EPOCH_REWARDS * BUS_COUNT as u64;
 
+    fn test_config(target_emmissions_rate: u64) -> Config {
+        let mut config = Config {
+            base_reward_rate: 1024,
+            last_reset_at: 0,
+            min_difficulty: 1,
+            target_emmissions_rate,
+            ..Zeroable::zeroed()
+        };
+        config.init_params();
+        config
+    }
+
     #[test]
-    fn test_get_target_emissions_rate() {
-        assert_eq!(get_target_emissions_rate(TARGET_MINUTE_REWARDS * 24 * 60 * 365 - 1), TARGET_MINUTE_REWARDS);
-        assert_eq!(get_target_emissions_rate(TARGET_MINUTE_REWARDS * 24 * 60 * 365), TARGET_MINUTE_REWARDS * 5 / 10);
+    fn test_emissions_at_supply() {
+        assert_eq!(emissions_at_supply(TARGET_MINUTE_REWARDS * 24 * 60 * 365 - 1), TARGET_MINUTE_REWARDS);
+        assert_eq!(emissions_at_supply(TARGET_MINUTE_REWARDS * 24 * 60 * 365), TARGET_MINUTE_REWARDS * 5 / 10);
+    }
+
+    #[test]
+    fn test_is_fee_proof() {
+        let key = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = vec![0; Proof::LEN];
+        data[0] = Proof::discriminator();
+        data[8..40].copy_from_slice(authority.as_ref());
+        let proof_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &gary_api::ID,
+            false,
+            0,
+        );
+        assert!(is_fee_proof(&proof_info, &authority));
+        assert!(!is_fee_proof(&proof_info, &Pubkey::new_unique()));
+
+        // A fee address which never opened a proof has no data.
+        let mut lamports = 0;
+        let mut data = vec![];
+        let missing_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &steel::system_program::ID,
+            false,
+            0,
+        );
+        assert!(!is_fee_proof(&missing_info, &authority));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_target() {
         let current_rate = 1000;
-        let new_rate =
-            calculate_new_reward_rate(current_rate, TARGET_EPOCH_REWARDS, TARGET_EPOCH_REWARDS);
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            TARGET_EPOCH_REWARDS,
+            TARGET_EPOCH_REWARDS,
+            INITIAL_SMOOTHING_FACTOR,
+        );
         assert!(new_rate.eq(&current_rate));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_div_by_zero() {
         let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, 0, TARGET_EPOCH_REWARDS);
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            0,
+            TARGET_EPOCH_REWARDS,
+            INITIAL_SMOOTHING_FACTOR,
+        );
         assert!(new_rate.eq(&current_rate));
     }
 
@@ -249,15 +556,20 @@ mod tests {
             current_rate,
             TARGET_EPOCH_REWARDS.saturating_add(10_000_000_000),
             TARGET_EPOCH_REWARDS,
+            INITIAL_SMOOTHING_FACTOR,
         );
         assert!(new_rate.lt(&current_rate));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_lower_edge() {
-        let current_rate = BASE_REWARD_RATE_MIN_THRESHOLD;
-        let new_rate =
-            calculate_new_reward_rate(current_rate, TARGET_EPOCH_REWARDS + 1, TARGET_EPOCH_REWARDS);
+        let current_rate = INITIAL_BASE_REWARD_RATE_MIN_THRESHOLD;
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            TARGET_EPOCH_REWARDS + 1,
+            TARGET_EPOCH_REWARDS,
+            INITIAL_SMOOTHING_FACTOR,
+        );
         assert!(new_rate.lt(&current_rate));
     }
 
@@ -268,8 +580,12 @@ mod tests {
             let current_rate: u64 = rng.sample(Uniform::new(1, TARGET_EPOCH_REWARDS));
             let actual_rewards: u64 =
                 rng.sample(Uniform::new(TARGET_EPOCH_REWARDS, MAX_EPOCH_REWARDS));
-            let new_rate =
-                calculate_new_reward_rate(current_rate, actual_rewards, TARGET_EPOCH_REWARDS);
+            let new_rate = calculate_new_reward_rate(
+                current_rate,
+                actual_rewards,
+                TARGET_EPOCH_REWARDS,
+                INITIAL_SMOOTHING_FACTOR,
+            );
             assert!(new_rate.lt(&current_rate));
         }
     }
@@ -281,6 +597,7 @@ mod tests {
             current_rate,
             TARGET_EPOCH_REWARDS.saturating_sub(10_000_000_000),
             TARGET_EPOCH_REWARDS,
+            INITIAL_SMOOTHING_FACTOR,
         );
         assert!(new_rate.gt(&current_rate));
     }
@@ -291,8 +608,12 @@ mod tests {
         for _ in 0..FUZZ_SIZE {
             let current_rate: u64 = rng.sample(Uniform::new(1, TARGET_EPOCH_REWARDS));
             let actual_rewards: u64 = rng.sample(Uniform::new(1, TARGET_EPOCH_REWARDS));
-            let new_rate =
-                calculate_new_reward_rate(current_rate, actual_rewards, TARGET_EPOCH_REWARDS);
+            let new_rate = calculate_new_reward_rate(
+                current_rate,
+                actual_rewards,
+                TARGET_EPOCH_REWARDS,
+                INITIAL_SMOOTHING_FACTOR,
+            );
             assert!(new_rate.gt(&current_rate));
         }
     }
@@ -300,15 +621,25 @@ mod tests {
     #[test]
     fn test_calculate_new_reward_rate_max_smooth() {
         let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, 1, TARGET_EPOCH_REWARDS);
-        assert!(new_rate.eq(&current_rate.saturating_mul(SMOOTHING_FACTOR)));
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            1,
+            TARGET_EPOCH_REWARDS,
+            INITIAL_SMOOTHING_FACTOR,
+        );
+        assert!(new_rate.eq(&current_rate.saturating_mul(INITIAL_SMOOTHING_FACTOR)));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_min_smooth() {
         let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, u64::MAX, TARGET_EPOCH_REWARDS);
-        assert!(new_rate.eq(&current_rate.saturating_div(SMOOTHING_FACTOR)));
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            u64::MAX,
+            TARGET_EPOCH_REWARDS,
+            INITIAL_SMOOTHING_FACTOR,
+        );
+        assert!(new_rate.eq(&current_rate.saturating_div(INITIAL_SMOOTHING_FACTOR)));
     }
 
     #[test]
@@ -317,72 +648,77 @@ mod tests {
             TARGET_EPOCH_REWARDS,
             MAX_EPOCH_REWARDS,
             TARGET_EPOCH_REWARDS,
+            INITIAL_SMOOTHING_FACTOR,
         );
-        assert!(new_rate.eq(&TARGET_EPOCH_REWARDS.saturating_div(SMOOTHING_FACTOR)));
+        assert!(new_rate.eq(&TARGET_EPOCH_REWARDS.saturating_div(INITIAL_SMOOTHING_FACTOR)));
     }
 
     #[test]
     fn test_calculate_new_reward_rate_min_inputs() {
-        let new_rate = calculate_new_reward_rate(1, 1, TARGET_EPOCH_REWARDS);
-        assert!(new_rate.eq(&1u64.saturating_mul(SMOOTHING_FACTOR)));
+        let new_rate =
+            calculate_new_reward_rate(1, 1, TARGET_EPOCH_REWARDS, INITIAL_SMOOTHING_FACTOR);
+        assert!(new_rate.eq(&1u64.saturating_mul(INITIAL_SMOOTHING_FACTOR)));
     }
 
     #[allow(deprecated)]
     #[test]
     fn test_process_epoch_simple() {
-        let mut config = Config {
-            base_reward_rate: 1024,
-            last_reset_at: 0,
-            min_difficulty: 1,
-            target_emmissions_rate: TARGET_MINUTE_REWARDS,
-        };
+        let mut config = test_config(TARGET_MINUTE_REWARDS);
         let bus_0 = &mut Bus {
             id: 0,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_1 = &mut Bus {
             id: 1,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_2 = &mut Bus {
             id: 2,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_3 = &mut Bus {
             id: 3,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_4 = &mut Bus {
             id: 4,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_5 = &mut Bus {
             id: 5,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_6 = &mut Bus {
             id: 6,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_7 = &mut Bus {
             id: 7,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let busses = [bus_0, bus_1, bus_2, bus_3, bus_4, bus_5, bus_6, bus_7];
         let clock = Clock::default();
@@ -394,70 +730,260 @@ mod tests {
             freeze_authority: COption::None,
         });
 
-        let amount_to_mint = config.process_epoch(busses, &clock, &mint).unwrap();
+        let amount_to_mint = config.process_epoch(busses, &mut EpochStats::zeroed(), &clock, &mint, 0).unwrap().amount_to_mint;
         assert_eq!(config.target_emmissions_rate, TARGET_MINUTE_REWARDS);
         assert_eq!(
-            TARGET_MINUTE_REWARDS * EPOCH_MINUTES as u64 * BUS_COUNT as u64,
+            TARGET_MINUTE_REWARDS * INITIAL_EPOCH_MINUTES as u64 * BUS_COUNT as u64,
             amount_to_mint
         );
     }
 
     #[allow(deprecated)]
     #[test]
-    fn test_process_epoch_emissions_boundary() {
-        let mut config = Config {
-            base_reward_rate: 1024,
-            last_reset_at: 0,
-            min_difficulty: 1,
-            target_emmissions_rate: TARGET_MINUTE_REWARDS,
+    fn test_process_epoch_custom_params() {
+        let mut config = test_config(TARGET_MINUTE_REWARDS);
+        config.epoch_minutes = 5;
+        config.base_reward_rate_min_threshold = 2048;
+        config.base_reward_rate_max_threshold = 8192;
+        let mut busses: [Bus; BUS_COUNT] = core::array::from_fn(|i| Bus {
+            id: i as u64,
+            rewards: 0,
+            theoretical_rewards: 0,
+            top_balance: 0,
+            ..Zeroable::zeroed()
+        });
+        let clock = Clock::default();
+        let mint = Mint::V0(spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: TARGET_MINUTE_REWARDS * 100,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        });
+
+        let amount_to_mint = config
+            .process_epoch(busses.each_mut(), &mut EpochStats::zeroed(), &clock, &mint, 0)
+            .unwrap()
+            .amount_to_mint;
+        assert_eq!(TARGET_MINUTE_REWARDS * 5 * BUS_COUNT as u64, amount_to_mint);
+        assert_eq!(config.base_reward_rate, 2048);
+        assert_eq!(config.min_difficulty, 2);
+    }
+
+    #[test]
+    fn test_calculate_epoch_work() {
+        assert_eq!(calculate_epoch_work(1024, 64, 3), 128);
+        assert_eq!(calculate_epoch_work(1024, 0, 0), 1024);
+        assert_eq!(calculate_epoch_work(u64::MAX, 1, 64), u64::MAX);
+    }
+
+    #[test]
+    fn test_calculate_ema() {
+        assert_eq!(calculate_ema(0, 1_000, 8), 1_000);
+        assert_eq!(calculate_ema(1_000, 1_800, 8), 1_100);
+        assert_eq!(calculate_ema(1_000, 200, 8), 900);
+        assert_eq!(calculate_ema(1_000, 5_000, 1), 5_000);
+        assert_eq!(calculate_ema(1_000, 5_000, 0), 5_000);
+    }
+
+    #[test]
+    fn test_calculate_ema_retarget() {
+        let target = 1 << 20;
+
+        // The min difficulty is kept while the rate is within the thresholds.
+        assert_eq!(calculate_ema_retarget(1 << 17, target, 5, 64, 512), (256, 5));
+        assert_eq!(calculate_ema_retarget(1 << 18, target, 5, 64, 512), (128, 5));
+
+        // Otherwise it moves by the fewest steps back within them.
+        assert_eq!(calculate_ema_retarget(1 << 20, target, 5, 64, 512), (64, 6));
+        assert_eq!(calculate_ema_retarget(1 << 15, target, 5, 64, 512), (512, 4));
+
+        // The rate is capped once the min difficulty cannot drop any further.
+        assert_eq!(calculate_ema_retarget(1, target, 5, 64, 512), (512, 1));
+        assert_eq!(calculate_ema_retarget(0, target, 5, 64, 512), (512, 1));
+    }
+
+    #[allow(deprecated)]
+    #[test]
+    fn test_process_epoch_ema() {
+        let mut config = test_config(TARGET_MINUTE_REWARDS);
+        config.retarget_mode = RetargetMode::Ema.into();
+        let mut busses: [Bus; BUS_COUNT] = core::array::from_fn(|i| Bus {
+            id: i as u64,
+            ..Zeroable::zeroed()
+        });
+        let clock = Clock::default();
+        let mint = Mint::V0(spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: TARGET_MINUTE_REWARDS * 100,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        });
+
+        // An epoch mined at 4x the target seeds the average, and pays out the target right away.
+        busses[0].theoretical_rewards = TARGET_EPOCH_REWARDS * 4;
+        config
+            .process_epoch(busses.each_mut(), &mut EpochStats::zeroed(), &clock, &mint, 0)
+            .unwrap();
+        assert_eq!(config.ema_epoch_work, TARGET_EPOCH_REWARDS * 4 * 2 / 1024);
+        assert_eq!((config.base_reward_rate, config.min_difficulty), (256, 1));
+
+        // An epoch without hashes leaves the rate and average unchanged.
+        let ema_epoch_work = config.ema_epoch_work;
+        config
+            .process_epoch(busses.each_mut(), &mut EpochStats::zeroed(), &clock, &mint, 0)
+            .unwrap();
+        assert_eq!(config.ema_epoch_work, ema_epoch_work);
+        assert_eq!((config.base_reward_rate, config.min_difficulty), (256, 1));
+
+        // A burst of 9x the work only moves the average by a window's share of it.
+        busses[0].theoretical_rewards = TARGET_EPOCH_REWARDS * 9;
+        config
+            .process_epoch(busses.each_mut(), &mut EpochStats::zeroed(), &clock, &mint, 0)
+            .unwrap();
+        assert_eq!(config.ema_epoch_work, ema_epoch_work * 2);
+        assert_eq!((config.base_reward_rate, config.min_difficulty), (128, 1));
+    }
+
+    #[allow(deprecated)]
+    #[test]
+    fn test_process_epoch_added_busses() {
+        let mut config = test_config(TARGET_MINUTE_REWARDS);
+        let mut busses: Vec<Bus> = (0..12)
+            .map(|i| Bus {
+                id: i,
+                rewards: 0,
+                theoretical_rewards: 0,
+                top_balance: 0,
+                ..Zeroable::zeroed()
+            })
+            .collect();
+        let clock = Clock::default();
+        let mint = Mint::V0(spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: TARGET_MINUTE_REWARDS * 100,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        });
+
+        let amount_to_mint = config
+            .process_epoch(busses.iter_mut(), &mut EpochStats::zeroed(), &clock, &mint, 0)
+            .unwrap()
+            .amount_to_mint;
+        assert_eq!(TARGET_EPOCH_REWARDS * 12, amount_to_mint);
+        assert!(busses.iter().all(|b| b.rewards == TARGET_EPOCH_REWARDS));
+    }
+
+    #[allow(deprecated)]
+    #[test]
+    fn test_process_epoch_records_stats() {
+        let mut config = test_config(TARGET_MINUTE_REWARDS);
+        let mut busses: Vec<Bus> = (0..2)
+            .map(|i| Bus {
+                id: i,
+                ..Zeroable::zeroed()
+            })
+            .collect();
+        busses[0].record_hash(1, 100);
+        busses[0].record_hash(3, 400);
+        busses[1].record_hash(3, 400);
+        busses[1].record_hash(40, 1_000);
+        busses[1].theoretical_rewards = 2_000;
+        let mut epoch_stats = EpochStats::zeroed();
+        let clock = Clock {
+            unix_timestamp: 1_000,
+            ..Clock::default()
         };
+        let mint = Mint::V0(spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: TARGET_MINUTE_REWARDS * 100,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        });
+
+        let amount_to_mint = config
+            .process_epoch(busses.iter_mut(), &mut epoch_stats, &clock, &mint, 0)
+            .unwrap()
+            .amount_to_mint;
+        let summary = epoch_stats.latest().unwrap();
+        assert_eq!(epoch_stats.count, 1);
+        assert_eq!(summary.ended_at, 1_000);
+        assert_eq!(summary.hashes, 4);
+        assert_eq!(summary.paid_rewards, 1_900);
+        assert_eq!(summary.theoretical_rewards, 2_000);
+        assert_eq!(summary.min_difficulty, 1);
+        assert_eq!(summary.base_reward_rate, 1024);
+        assert_eq!(summary.amount_minted, amount_to_mint);
+        assert_eq!(summary.difficulty_histogram[1], 1);
+        assert_eq!(summary.difficulty_histogram[3], 2);
+        assert_eq!(summary.difficulty_histogram[DIFFICULTY_HISTOGRAM_LEN - 1], 1);
+        assert!(busses
+            .iter()
+            .all(|b| b.hashes == 0 && b.paid_rewards == 0 && b.difficulty_histogram == [0; DIFFICULTY_HISTOGRAM_LEN]));
+    }
+
+    #[allow(deprecated)]
+    #[test]
+    fn test_process_epoch_emissions_boundary() {
+        let mut config = test_config(TARGET_MINUTE_REWARDS);
         let bus_0 = &mut Bus {
             id: 0,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_1 = &mut Bus {
             id: 1,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_2 = &mut Bus {
             id: 2,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_3 = &mut Bus {
             id: 3,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_4 = &mut Bus {
             id: 4,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_5 = &mut Bus {
             id: 5,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_6 = &mut Bus {
             id: 6,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_7 = &mut Bus {
             id: 7,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let busses = [bus_0, bus_1, bus_2, bus_3, bus_4, bus_5, bus_6, bus_7];
         let clock = Clock::default();
@@ -469,10 +995,10 @@ mod tests {
             freeze_authority: COption::None,
         });
 
-        let amount_to_mint = config.process_epoch(busses, &clock, &mint).unwrap();
-        assert_eq!(config.target_emmissions_rate, 90_000_000_000);
+        let amount_to_mint = config.process_epoch(busses, &mut EpochStats::zeroed(), &clock, &mint, 0).unwrap().amount_to_mint;
+        assert_eq!(config.target_emmissions_rate, TARGET_MINUTE_REWARDS / 2);
         assert_eq!(
-            90_000_000_000 * EPOCH_MINUTES as u64 * BUS_COUNT as u64,
+            TARGET_MINUTE_REWARDS / 2 * INITIAL_EPOCH_MINUTES as u64 * BUS_COUNT as u64,
             amount_to_mint
         );
     }
@@ -480,131 +1006,137 @@ mod tests {
     #[allow(deprecated)]
     #[test]
     fn test_process_epoch_max_supply() {
-        let mut config = Config {
-            base_reward_rate: 1024,
-            last_reset_at: 0,
-            min_difficulty: 1,
-            target_emmissions_rate: 5_233_476_327,
-        };
+        let mut config = test_config(5_233_476_327);
         let bus_0 = &mut Bus {
             id: 0,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_1 = &mut Bus {
             id: 1,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_2 = &mut Bus {
             id: 2,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_3 = &mut Bus {
             id: 3,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_4 = &mut Bus {
             id: 4,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_5 = &mut Bus {
             id: 5,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_6 = &mut Bus {
             id: 6,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_7 = &mut Bus {
             id: 7,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let busses = [bus_0, bus_1, bus_2, bus_3, bus_4, bus_5, bus_6, bus_7];
         let clock = Clock::default();
         let mint = Mint::V0(spl_token::state::Mint {
             mint_authority: COption::None,
-            supply: TARGET_MINUTE_REWARDS * 4_999_999,
+            supply: MAX_SUPPLY - TARGET_MINUTE_REWARDS,
             decimals: TOKEN_DECIMALS,
             is_initialized: true,
             freeze_authority: COption::None,
         });
 
-        let amount_to_mint = config.process_epoch(busses, &clock, &mint).unwrap();
-        assert_eq!(config.target_emmissions_rate, 5_233_476_327);
-        assert_eq!(TARGET_MINUTE_REWARDS, amount_to_mint);
+        let amount_to_mint = config.process_epoch(busses, &mut EpochStats::zeroed(), &clock, &mint, 0).unwrap().amount_to_mint;
+        assert_eq!(config.target_emmissions_rate, TAIL_EMISSIONS_RATE);
+        assert_eq!(0, amount_to_mint);
     }
 
     #[allow(deprecated)]
     #[test]
     fn test_process_epoch_zero_emissions() {
-        let mut config = Config {
-            base_reward_rate: 1024,
-            last_reset_at: 0,
-            min_difficulty: 1,
-            target_emmissions_rate: 5_233_476_327,
-        };
+        let mut config = test_config(5_233_476_327);
         let bus_0 = &mut Bus {
             id: 0,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_1 = &mut Bus {
             id: 1,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_2 = &mut Bus {
             id: 2,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_3 = &mut Bus {
             id: 3,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_4 = &mut Bus {
             id: 4,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_5 = &mut Bus {
             id: 5,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_6 = &mut Bus {
             id: 6,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let bus_7 = &mut Bus {
             id: 7,
             rewards: 0,
             theoretical_rewards: 0,
             top_balance: 0,
+            ..Zeroable::zeroed()
         };
         let busses = [bus_0, bus_1, bus_2, bus_3, bus_4, bus_5, bus_6, bus_7];
         let clock = Clock::default();
@@ -616,7 +1148,181 @@ mod tests {
             freeze_authority: COption::None,
         });
 
-        let amount_to_mint = config.process_epoch(busses, &clock, &mint);
-        assert!(amount_to_mint.is_err());
+        let outcome = config
+            .process_epoch(busses, &mut EpochStats::zeroed(), &clock, &mint, 0)
+            .unwrap();
+        assert_eq!(outcome, EpochOutcome::default());
+        assert_eq!(config.target_emmissions_rate, 0);
+        assert_eq!(config.base_reward_rate, 1024);
+        assert_eq!(config.min_difficulty, 1);
+    }
+
+    #[allow(deprecated)]
+    #[test]
+    fn test_process_epoch_reset_reward() {
+        let mut config = test_config(TARGET_MINUTE_REWARDS);
+        let mut busses: [Bus; BUS_COUNT] = core::array::from_fn(|i| Bus {
+            id: i as u64,
+            ..Zeroable::zeroed()
+        });
+        let clock = Clock::default();
+        let mint = Mint::V0(spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: TARGET_MINUTE_REWARDS * 100,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        });
+
+        // The crank reward is minted on top of the top ups, so every bus is fully funded.
+        let outcome = config
+            .process_epoch(
+                busses.each_mut(),
+                &mut EpochStats::zeroed(),
+                &clock,
+                &mint,
+                INITIAL_RESET_REWARD,
+            )
+            .unwrap();
+        let bus_rewards: u64 = busses.iter().map(|b| b.rewards).sum();
+        assert_eq!(outcome.reset_reward, INITIAL_RESET_REWARD);
+        assert_eq!(
+            outcome.amount_to_mint,
+            TARGET_EPOCH_REWARDS * BUS_COUNT as u64 + INITIAL_RESET_REWARD
+        );
+        assert_eq!(bus_rewards + outcome.reset_reward, outcome.amount_to_mint);
+        assert!(busses.iter().all(|b| b.rewards == TARGET_EPOCH_REWARDS));
+
+        // The reward is paid in full when few miners are active and the busses are still full.
+        let outcome = config
+            .process_epoch(
+                busses.each_mut(),
+                &mut EpochStats::zeroed(),
+                &clock,
+                &mint,
+                INITIAL_RESET_REWARD,
+            )
+            .unwrap();
+        assert_eq!(outcome.reset_reward, INITIAL_RESET_REWARD);
+        assert_eq!(outcome.amount_to_mint, INITIAL_RESET_REWARD);
+
+        // Once emissions have ended, it is paid out of the reward pool.
+        let mint = Mint::V0(spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: MAX_SUPPLY,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        });
+        config.reward_pool = INITIAL_RESET_REWARD * 3;
+        let outcome = config
+            .process_epoch(
+                busses.each_mut(),
+                &mut EpochStats::zeroed(),
+                &clock,
+                &mint,
+                INITIAL_RESET_REWARD,
+            )
+            .unwrap();
+        assert_eq!(outcome.reset_reward, INITIAL_RESET_REWARD);
+        assert_eq!(outcome.amount_to_mint, 0);
+
+        // The reward is capped by the reward pool.
+        config.reward_pool = 1;
+        for bus in busses.iter_mut() {
+            bus.rewards = 0;
+        }
+        let outcome = config
+            .process_epoch(
+                busses.each_mut(),
+                &mut EpochStats::zeroed(),
+                &clock,
+                &mint,
+                INITIAL_RESET_REWARD,
+            )
+            .unwrap();
+        assert_eq!(outcome.reset_reward, 1);
+        assert_eq!(config.reward_pool, 0);
+    }
+
+    #[allow(deprecated)]
+    #[test]
+    fn test_simulate_end_of_emissions() {
+        let mut config = test_config(TARGET_MINUTE_REWARDS);
+        let mut busses: Vec<Bus> = (0..BUS_COUNT as u64)
+            .map(|i| Bus {
+                id: i,
+                ..Zeroable::zeroed()
+            })
+            .collect();
+        let mut epoch_stats = EpochStats::zeroed();
+
+        // Start a few epochs before the emissions schedule runs out.
+        let last_period = EMISSIONS_SCHEDULE[EMISSIONS_HALVINGS - 1];
+        let last_rate = EMISSIONS_SCHEDULE
+            .iter()
+            .find(|p| p.supply_threshold == last_period.supply_threshold)
+            .unwrap()
+            .rate;
+        let mut supply = last_period.supply_threshold
+            - last_rate * INITIAL_EPOCH_MINUTES as u64 * BUS_COUNT as u64 * 3;
+
+        let mut terminal_epochs = 0;
+        let mut terminal_paid_rewards = 0u64;
+        let mut recycled_fees = 0u64;
+        for i in 0..100 {
+            let mint = Mint::V0(spl_token::state::Mint {
+                mint_authority: COption::None,
+                supply,
+                decimals: TOKEN_DECIMALS,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            });
+            let terminal = emissions_at_supply(supply) == 0;
+            let accrued_fees: u64 = busses.iter().map(|b| b.taxes + b.fines).sum();
+            let pool_before = config.reward_pool;
+            let clock = Clock {
+                unix_timestamp: i,
+                ..Clock::default()
+            };
+
+            // Resets keep advancing the epoch after emissions end.
+            let outcome = config
+                .process_epoch(busses.iter_mut(), &mut epoch_stats, &clock, &mint, 0)
+                .unwrap();
+            assert_eq!(config.last_reset_at, i);
+            supply += outcome.amount_to_mint;
+            assert!(supply <= MAX_SUPPLY);
+
+            // Once emissions end, nothing is minted and the busses are funded by recycled fees.
+            let topups: u64 = busses.iter().map(|b| b.rewards).sum();
+            if terminal {
+                terminal_epochs += 1;
+                assert_eq!(outcome.amount_to_mint, 0);
+                let recycled = accrued_fees - outcome.taxes - outcome.fines;
+                assert!(recycled <= accrued_fees * config.recycle_percent / FEE_PERCENT_DENOMINATOR);
+                recycled_fees += recycled;
+                assert_eq!(pool_before + recycled, config.reward_pool + topups);
+            }
+
+            // Miners drain every bus, paying taxes and fines on their rewards.
+            for bus in busses.iter_mut() {
+                let paid = bus.rewards;
+                if terminal {
+                    terminal_paid_rewards += paid;
+                }
+                bus.record_hash(config.min_difficulty as u32, paid);
+                bus.theoretical_rewards = paid;
+                bus.taxes += paid * config.taxes_percent / FEE_PERCENT_DENOMINATOR;
+                bus.fines += paid * config.fines_percent / FEE_PERCENT_DENOMINATOR;
+                bus.rewards = 0;
+            }
+        }
+
+        // Miners keep earning in terminal mode, and never more than the recycled fees.
+        assert!(terminal_epochs > 90);
+        assert!(terminal_paid_rewards > 0);
+        assert!(terminal_paid_rewards <= recycled_fees);
+        assert_eq!(epoch_stats.count, 100);
     }
 }
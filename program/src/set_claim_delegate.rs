@@ -0,0 +1,32 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// SetClaimDelegate sets or clears the key allowed to claim a proof's rewards to a fixed beneficiary.
+pub fn process_set_claim_delegate(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SetClaimDelegate::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    proof_info.is_writable()?.as_proof()?.assert_err(
+        |p| p.authority == *signer_info.key,
+        ProgramError::MissingRequiredSignature,
+    )?;
+    let extension = proof_info.as_proof_extension_mut()?;
+
+    // Update the claim delegate.
+    //
+    // Clearing the delegate also clears the beneficiary.
+    if args.delegate == Pubkey::default() {
+        extension.claim_delegate = Pubkey::default();
+        extension.claim_beneficiary = Pubkey::default();
+    } else {
+        extension.claim_delegate = args.delegate;
+        extension.claim_beneficiary = args.beneficiary;
+    }
+
+    Ok(())
+}
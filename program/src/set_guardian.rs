@@ -0,0 +1,26 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// SetGuardian updates the authority allowed to pause instructions.
+pub fn process_set_guardian(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SetGuardian::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .is_config()?
+        .is_writable()?
+        .as_account_mut::<Config>(&gary_api::ID)?
+        .assert_mut(|c| c.admin == *signer_info.key)?;
+
+    // Update the guardian.
+    //
+    // Setting the default pubkey removes the guardian.
+    config.guardian = args.guardian;
+
+    Ok(())
+}
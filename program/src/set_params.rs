@@ -0,0 +1,88 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// SetParams updates the tunable economic parameters of the program.
+///
+/// The proofs of the new taxes and fines addresses must already be open, since reset sweeps the
+/// fees into them.
+pub fn process_set_params(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SetParams::try_from_bytes(data)?;
+    let taxes_percent = u64::from_le_bytes(args.taxes_percent);
+    let fines_percent = u64::from_le_bytes(args.fines_percent);
+    let tolerance = i64::from_le_bytes(args.tolerance);
+    let epoch_minutes = i64::from_le_bytes(args.epoch_minutes);
+    let smoothing_factor = u64::from_le_bytes(args.smoothing_factor);
+    let base_reward_rate_min_threshold = u64::from_le_bytes(args.base_reward_rate_min_threshold);
+    let base_reward_rate_max_threshold = u64::from_le_bytes(args.base_reward_rate_max_threshold);
+    let reset_reward = u64::from_le_bytes(args.reset_reward);
+    let recycle_percent = u64::from_le_bytes(args.recycle_percent);
+    let retarget_mode = u64::from_le_bytes(args.retarget_mode);
+    let ema_window = u64::from_le_bytes(args.ema_window);
+    let referral_percent = u64::from_le_bytes(args.referral_percent);
+    let referral_window = i64::from_le_bytes(args.referral_window);
+
+    // Load accounts.
+    let [signer_info, config_info, taxes_proof_info, fines_proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .is_config()?
+        .is_writable()?
+        .as_account_mut::<Config>(&gary_api::ID)?
+        .assert_mut(|c| c.admin == *signer_info.key)?;
+    taxes_proof_info.as_proof()?.assert_err(
+        |p| p.authority == args.taxes_address,
+        GaryError::InvalidParams.into(),
+    )?;
+    fines_proof_info.as_proof()?.assert_err(
+        |p| p.authority == args.fines_address,
+        GaryError::InvalidParams.into(),
+    )?;
+
+    // Validate the parameters are within bounds.
+    //
+    // The reward rate thresholds must be at least a factor of 2 apart, otherwise doubling or
+    // halving the base reward rate at a threshold could cross the other one.
+    if taxes_percent.saturating_add(fines_percent) > MAX_FEES_PERCENT
+        || !(0..=MAX_TOLERANCE).contains(&tolerance)
+        || !(1..=MAX_EPOCH_MINUTES).contains(&epoch_minutes)
+        || !(2..=MAX_SMOOTHING_FACTOR).contains(&smoothing_factor)
+        || base_reward_rate_min_threshold == 0
+        || base_reward_rate_max_threshold < base_reward_rate_min_threshold.saturating_mul(2)
+        || reset_reward > MAX_RESET_REWARD
+        || recycle_percent > FEE_PERCENT_DENOMINATOR
+        || RetargetMode::try_from(retarget_mode).is_err()
+        || !(1..=MAX_EMA_WINDOW).contains(&ema_window)
+        || referral_percent > FEE_PERCENT_DENOMINATOR
+        || !(0..=MAX_REFERRAL_WINDOW).contains(&referral_window)
+    {
+        return Err(GaryError::InvalidParams.into());
+    }
+
+    // Update the parameters.
+    config.taxes_address = args.taxes_address;
+    config.fines_address = args.fines_address;
+    config.taxes_percent = taxes_percent;
+    config.fines_percent = fines_percent;
+    config.tolerance = tolerance;
+    config.epoch_minutes = epoch_minutes;
+    config.smoothing_factor = smoothing_factor;
+    config.base_reward_rate_min_threshold = base_reward_rate_min_threshold;
+    config.base_reward_rate_max_threshold = base_reward_rate_max_threshold;
+    config.reset_reward = reset_reward;
+    config.recycle_percent = recycle_percent;
+    config.referral_percent = referral_percent;
+    config.referral_window = referral_window;
+
+    // Restart the moving average when switching retarget modes, so it is seeded from the next
+    // epoch rather than a stale one.
+    if config.retarget_mode != retarget_mode {
+        config.ema_epoch_work = 0;
+    }
+    config.retarget_mode = retarget_mode;
+    config.ema_window = ema_window;
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use gary_api::prelude::*;
+use steel::*;
+
+/// SetPaused updates the paused instructions. The guardian may pause instructions immediately,
+/// but only the admin may unpause them. The paused instructions are copied to the treasury, which
+/// claims read them from.
+pub fn process_set_paused(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SetPaused::try_from_bytes(data)?;
+    let paused = u64::from_le_bytes(args.paused);
+
+    // Load accounts.
+    let [signer_info, config_info, treasury_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .is_config()?
+        .is_writable()?
+        .as_account_mut::<Config>(&gary_api::ID)?
+        .assert_mut(|c| c.admin == *signer_info.key || c.guardian == *signer_info.key)?;
+    let treasury = treasury_info
+        .is_treasury()?
+        .is_writable()?
+        .as_account_mut::<Treasury>(&gary_api::ID)?;
+
+    // Validate the change is allowed for the signer.
+    let is_admin = config.admin == *signer_info.key;
+    validate_paused(config.paused, paused, is_admin)?;
+
+    // Update the paused instructions.
+    config.paused = paused;
+    treasury.paused = paused;
+
+    Ok(())
+}
+
+/// Validates a change of the paused instructions from `current` to `paused`.
+///
+/// Unknown flags are rejected. Only the admin may clear a flag, so a compromised guardian key can
+/// halt the program but cannot resume it.
+fn validate_paused(current: u64, paused: u64, is_admin: bool) -> Result<(), ProgramError> {
+    if paused & !PAUSE_ALL != 0 {
+        return Err(GaryError::InvalidParams.into());
+    }
+    if !is_admin && paused & current != current {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use gary_api::{consts::*, error::GaryError};
+    use steel::ProgramError;
+
+    use super::validate_paused;
+
+    #[test]
+    fn test_validate_paused() {
+        // The guardian may add flags, but not clear them.
+        assert_eq!(validate_paused(0, PAUSE_MINE, false), Ok(()));
+        assert_eq!(validate_paused(PAUSE_MINE, PAUSE_ALL, false), Ok(()));
+        assert_eq!(
+            validate_paused(PAUSE_MINE | PAUSE_CLAIM, PAUSE_CLAIM | PAUSE_RESET, false),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        assert_eq!(
+            validate_paused(PAUSE_ALL, 0, false),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+
+        // The admin may set any known flags.
+        assert_eq!(validate_paused(PAUSE_ALL, 0, true), Ok(()));
+        assert_eq!(validate_paused(PAUSE_MINE, PAUSE_RESET, true), Ok(()));
+        assert_eq!(
+            validate_paused(0, PAUSE_ALL + 1, true),
+            Err(GaryError::InvalidParams.into())
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::mem::size_of;
+
+use gary_api::prelude::*;
+use solana_program::{keccak::hashv, slot_hashes::SlotHash};
+use steel::*;
+
+/// TransferProof moves a proof's balance and lifetime stats into a new proof account owned by
+/// a new authority, and closes the old proof.
+pub fn process_transfer_proof(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, new_authority_info, proof_info, new_proof_info, system_program, slot_hashes_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    new_authority_info.is_signer()?;
+    let proof = *proof_info
+        .is_writable()?
+        .as_proof()?
+        .assert_err(
+            |p| p.authority == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?;
+    new_proof_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[PROOF, new_authority_info.key.as_ref()], &gary_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    slot_hashes_info.is_sysvar(&sysvar::slot_hashes::ID)?;
+
+    // Initialize the new proof, paid for by the new authority.
+    allocate_account(
+        new_proof_info,
+        system_program,
+        new_authority_info,
+        Proof::LEN,
+        &gary_api::ID,
+        &[PROOF, new_authority_info.key.as_ref()],
+    )?;
+    new_proof_info.data.borrow_mut()[0] = Proof::discriminator();
+    ProofExtension::init_account_data(&mut new_proof_info.data.borrow_mut())?;
+
+    // Copy the proof state to the new authority.
+    //
+    // Claim delegates and miner keys set by the old authority do not carry over. The referral
+    // does, so a referred miner cannot end the referral window early by transferring the proof.
+    // Legacy proofs have no extension, so they have no referral to copy.
+    if let Ok(extension) = proof_info.as_proof_extension() {
+        let (referrer, opened_at) = (extension.referrer, extension.opened_at);
+        let new_extension = new_proof_info.as_proof_extension_mut()?;
+        new_extension.referrer = referrer;
+        new_extension.opened_at = opened_at;
+    }
+    let challenge = hashv(&[
+        new_authority_info.key.as_ref(),
+        &slot_hashes_info.data.borrow()[0..size_of::<SlotHash>()],
+    ])
+    .0;
+    let new_proof = new_proof_info.as_proof_mut()?;
+    *new_proof = transferred_proof(&proof, *new_authority_info.key, challenge);
+
+    // Return rent of the old proof to the old authority.
+    proof_info.close(signer_info)?;
+
+    // Log the transfer.
+    TransferProofEvent {
+        disc: GaryEvent::TransferProof as u64,
+        authority: *signer_info.key,
+        new_authority: *new_authority_info.key,
+        balance: proof.balance,
+        ts: Clock::get()?.unix_timestamp,
+    }
+    .log();
+
+    Ok(())
+}
+
+/// Returns the state of a proof transferred to a new authority.
+///
+/// The balance and lifetime stats carry over. The new authority replaces the old miner key, since
+/// a transfer is how a compromised or rotated wallet is left behind, and the challenge is reset
+/// like a newly opened proof.
+fn transferred_proof(proof: &Proof, new_authority: Pubkey, challenge: [u8; 32]) -> Proof {
+    Proof {
+        authority: new_authority,
+        miner: new_authority,
+        challenge,
+        ..*proof
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gary_api::state::Proof;
+    use steel::*;
+
+    use super::transferred_proof;
+
+    #[test]
+    fn test_transferred_proof() {
+        let proof = Proof {
+            authority: Pubkey::new_unique(),
+            balance: 100,
+            challenge: [1; 32],
+            last_hash: [2; 32],
+            last_hash_at: 3,
+            last_claim_at: 4,
+            miner: Pubkey::new_unique(),
+            total_hashes: 5,
+            total_rewards: 6,
+        };
+        let new_authority = Pubkey::new_unique();
+        let new_proof = transferred_proof(&proof, new_authority, [9; 32]);
+
+        // The old miner key is no longer authorized, and the challenge is refreshed.
+        assert_eq!(new_proof.authority, new_authority);
+        assert_eq!(new_proof.miner, new_authority);
+        assert_eq!(new_proof.challenge, [9; 32]);
+
+        // The balance and lifetime stats carry over.
+        assert_eq!(new_proof.balance, proof.balance);
+        assert_eq!(new_proof.last_hash_at, proof.last_hash_at);
+        assert_eq!(new_proof.total_hashes, proof.total_hashes);
+        assert_eq!(new_proof.total_rewards, proof.total_rewards);
+    }
+}
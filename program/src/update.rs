@@ -9,7 +9,7 @@ pub fn process_update(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResu
     };
     signer_info.is_signer()?;
     let proof = proof_info
-        .as_account_mut::<Proof>(&gary_api::ID)?
+        .as_proof_mut()?
         .assert_mut_err(
             |p| p.authority == *signer_info.key,
             ProgramError::MissingRequiredSignature,
@@ -18,5 +18,14 @@ pub fn process_update(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResu
     // Update the proof's miner authority.
     proof.miner = *miner_info.key;
 
+    // Log the update.
+    UpdateEvent {
+        disc: GaryEvent::Update as u64,
+        authority: proof.authority,
+        miner: proof.miner,
+        ts: Clock::get()?.unix_timestamp,
+    }
+    .log();
+
     Ok(())
 }
@@ -0,0 +1,431 @@
+//! Simulates the GARY emissions schedule and difficulty adjustment over many epochs.
+//!
+//! Mining is modelled off-chain from synthetic hashpower and miner count traces, while every
+//! reset runs the program's own epoch processing. This answers questions like what happens to
+//! the min difficulty and base reward rate if hashpower doubles, without going to mainnet.
+
+mod trace;
+
+pub use trace::*;
+
+use gary_api::prelude::*;
+use gary_program::{pay_from_bus, EpochProcessor};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+use solana_program::program_option::COption;
+use steel::*;
+
+/// EpochInput is the network activity during a simulated epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct EpochInput {
+    /// The total hashpower of the network, in hashes per second.
+    pub hashpower: f64,
+
+    /// The number of miners the hashpower is evenly split between.
+    pub miners: u64,
+}
+
+/// EpochRecord is the state of the network at the end of a simulated epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct EpochRecord {
+    /// The number of the epoch, starting from 1.
+    pub epoch: u64,
+
+    /// The timestamp of the reset which ended the epoch.
+    pub ended_at: i64,
+
+    /// The hashpower of the network during the epoch.
+    pub hashpower: f64,
+
+    /// The number of miners during the epoch.
+    pub miners: u64,
+
+    /// The token supply after the reset.
+    pub supply: u64,
+
+    /// The base reward rate set by the reset for the next epoch.
+    pub base_reward_rate: u64,
+
+    /// The min difficulty set by the reset for the next epoch.
+    pub min_difficulty: u64,
+
+    /// The target emissions rate set by the reset for the next epoch.
+    pub target_emissions_rate: u64,
+
+    /// The unallocated reward pool after the reset.
+    pub reward_pool: u64,
+
+    /// The number of hashes accepted during the epoch.
+    pub hashes: u64,
+
+    /// The rewards which would have been paid out if there were no bus limits.
+    pub theoretical_rewards: u64,
+
+    /// The rewards actually paid out by the busses.
+    pub paid_rewards: u64,
+
+    /// The amount minted by the reset.
+    pub amount_minted: u64,
+
+    /// The number of busses which ran out of rewards during the epoch.
+    pub exhausted_busses: u64,
+
+    /// The minute of the epoch in which the first bus ran out of rewards, if any did.
+    pub first_exhausted_minute: Option<u64>,
+}
+
+/// Simulator holds the program state of a simulated network.
+pub struct Simulator {
+    /// The program config.
+    pub config: Config,
+
+    /// The bus accounts.
+    pub busses: Vec<Bus>,
+
+    /// The epoch stats account.
+    pub epoch_stats: EpochStats,
+
+    /// The token supply.
+    pub supply: u64,
+
+    /// The number of epochs simulated.
+    pub epoch: u64,
+
+    /// The percent of hashes submitted by proofs whose referral window is still open.
+    pub referred_percent: u64,
+
+    /// The source of randomness for hash difficulties.
+    rng: StdRng,
+}
+
+impl Simulator {
+    /// Creates a simulator with the state of a newly initialized program, after the first reset
+    /// has funded the busses.
+    pub fn new(seed: u64) -> Result<Self, ProgramError> {
+        let mut config = Config {
+            base_reward_rate: INITIAL_BASE_REWARD_RATE,
+            min_difficulty: INITIAL_MIN_DIFFICULTY as u64,
+            bus_count: BUS_COUNT as u64,
+            ..Zeroable::zeroed()
+        };
+        config.init_params();
+        let busses = (0..config.bus_count)
+            .map(|id| Bus {
+                id,
+                ..Zeroable::zeroed()
+            })
+            .collect();
+        let mut sim = Self::from_state(config, busses, 0, seed);
+        sim.reset()?;
+        Ok(sim)
+    }
+
+    /// Creates a simulator from existing program state, such as the accounts on mainnet.
+    pub fn from_state(config: Config, busses: Vec<Bus>, supply: u64, seed: u64) -> Self {
+        Simulator {
+            config,
+            busses,
+            epoch_stats: EpochStats::zeroed(),
+            supply,
+            epoch: 0,
+            referred_percent: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Switches the algorithm which retargets the reward rate and difficulty at each reset.
+    pub fn set_retarget_mode(&mut self, mode: RetargetMode, ema_window: u64) {
+        if self.config.retarget_mode() != mode {
+            self.config.ema_epoch_work = 0;
+        }
+        self.config.retarget_mode = mode.into();
+        self.config.ema_window = ema_window;
+    }
+
+    /// Simulates every epoch of a trace.
+    pub fn run(&mut self, trace: &[EpochInput]) -> Result<Vec<EpochRecord>, ProgramError> {
+        trace.iter().map(|input| self.step(input)).collect()
+    }
+
+    /// Simulates a single epoch, followed by a reset cranked on time.
+    pub fn step(&mut self, input: &EpochInput) -> Result<EpochRecord, ProgramError> {
+        let first_exhausted_minute = self.mine(input);
+        let exhausted_busses = self.busses.iter().filter(|b| b.rewards == 0).count() as u64;
+        let amount_minted = self.reset()?;
+        let summary = self
+            .epoch_stats
+            .latest()
+            .copied()
+            .unwrap_or(Zeroable::zeroed());
+        self.epoch += 1;
+        Ok(EpochRecord {
+            epoch: self.epoch,
+            ended_at: summary.ended_at,
+            hashpower: input.hashpower,
+            miners: input.miners,
+            supply: self.supply,
+            base_reward_rate: self.config.base_reward_rate,
+            min_difficulty: self.config.min_difficulty,
+            target_emissions_rate: self.config.target_emmissions_rate,
+            reward_pool: self.config.reward_pool,
+            hashes: summary.hashes,
+            theoretical_rewards: summary.theoretical_rewards,
+            paid_rewards: summary.paid_rewards,
+            amount_minted,
+            exhausted_busses,
+            first_exhausted_minute,
+        })
+    }
+
+    /// Submits one hash per miner per minute of the epoch, each to a random bus. Returns the
+    /// minute in which the first bus ran out of rewards.
+    fn mine(&mut self, input: &EpochInput) -> Option<u64> {
+        let mut first_exhausted_minute = None;
+        if input.miners == 0 || self.busses.is_empty() {
+            return first_exhausted_minute;
+        }
+        let hashes_per_miner = input.hashpower * ONE_MINUTE as f64 / input.miners as f64;
+        for minute in 0..self.config.epoch_minutes as u64 {
+            for _ in 0..input.miners {
+                let difficulty = self.sample_difficulty(hashes_per_miner);
+                if difficulty < self.config.min_difficulty as u32 {
+                    continue;
+                }
+                let i = self.rng.gen_range(0..self.busses.len());
+                let referred =
+                    self.referred_percent > 0 && self.rng.gen_range(0..100) < self.referred_percent;
+                pay_hash(&self.config, &mut self.busses[i], difficulty, referred);
+            }
+            if first_exhausted_minute.is_none() && self.busses.iter().any(|b| b.rewards == 0) {
+                first_exhausted_minute = Some(minute);
+            }
+        }
+        first_exhausted_minute
+    }
+
+    /// Samples the best difficulty a miner finds in the given number of hashes.
+    ///
+    /// Each hash has `1 / 2^d` odds of at least `d` leading zeros, so the best difficulty of `n`
+    /// hashes is below `d` with odds of about `exp(-n / 2^d)`. Inverting that gives
+    /// `log2(n / e)` for an exponentially distributed `e`.
+    fn sample_difficulty(&mut self, hashes: f64) -> u32 {
+        let e = -(1.0 - self.rng.gen::<f64>()).ln();
+        (hashes / e).log2().floor().clamp(0.0, 64.0) as u32
+    }
+
+    /// Runs the epoch processing of the reset instruction and credits the crank reward. Returns
+    /// the amount minted.
+    fn reset(&mut self) -> Result<u64, ProgramError> {
+        let clock = Clock {
+            unix_timestamp: self.config.last_reset_at + self.config.epoch_duration(),
+            ..Clock::default()
+        };
+        let mint = Mint::V0(spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: self.supply,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        });
+        let outcome = self.config.process_epoch(
+            self.busses.iter_mut(),
+            &mut self.epoch_stats,
+            &clock,
+            &mint,
+            self.config.reset_reward,
+        )?;
+        self.supply += outcome.amount_to_mint;
+        Ok(outcome.amount_to_mint)
+    }
+}
+
+/// Pays out a hash the way the mine instruction does, for a miner submitting on time without a
+/// boost.
+fn pay_hash(config: &Config, bus: &mut Bus, difficulty: u32, referred: bool) {
+    let normalized_difficulty = difficulty - config.min_difficulty as u32;
+    let gross_reward = config
+        .base_reward_rate
+        .saturating_mul(2u64.saturating_pow(normalized_difficulty));
+    pay_from_bus(config, bus, difficulty, gross_reward, referred);
+}
+
+/// Summary is the stability of payouts over a simulation.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Summary {
+    /// The number of epochs summarized.
+    pub epochs: usize,
+
+    /// The mean rewards paid out per epoch.
+    pub mean_paid_rewards: f64,
+
+    /// The standard deviation of the rewards paid out per epoch, relative to the mean.
+    pub paid_rewards_cv: f64,
+
+    /// The mean change of the reward per unit of work from one epoch to the next, in bits. A hash
+    /// of any difficulty pays out `base_reward_rate / 2^min_difficulty` per unit of work.
+    pub mean_rate_swing: f64,
+
+    /// The number of times the min difficulty changed.
+    pub difficulty_changes: u64,
+}
+
+/// Summarizes the payout stability of epoch records, skipping the first `warmup` epochs while the
+/// network settles.
+pub fn summarize(records: &[EpochRecord], warmup: usize) -> Summary {
+    let records = records.get(warmup..).unwrap_or_default();
+    let n = records.len().max(1) as f64;
+    let mean_paid_rewards = records.iter().map(|r| r.paid_rewards as f64).sum::<f64>() / n;
+    let variance = records
+        .iter()
+        .map(|r| (r.paid_rewards as f64 - mean_paid_rewards).powi(2))
+        .sum::<f64>()
+        / n;
+    let rate = |r: &EpochRecord| (r.base_reward_rate as f64).log2() - r.min_difficulty as f64;
+    let swings = records
+        .windows(2)
+        .map(|w| (rate(&w[1]) - rate(&w[0])).abs());
+    Summary {
+        epochs: records.len(),
+        mean_paid_rewards,
+        paid_rewards_cv: if mean_paid_rewards > 0.0 {
+            variance.sqrt() / mean_paid_rewards
+        } else {
+            0.0
+        },
+        mean_rate_swing: swings.sum::<f64>() / (n - 1.0).max(1.0),
+        difficulty_changes: records
+            .windows(2)
+            .filter(|w| w[0].min_difficulty != w[1].min_difficulty)
+            .count() as u64,
+    }
+}
+
+/// Formats epoch records as CSV, with a header row.
+pub fn to_csv(records: &[EpochRecord]) -> String {
+    let mut csv = String::from(
+        "epoch,ended_at,hashpower,miners,supply,base_reward_rate,min_difficulty,\
+         target_emissions_rate,reward_pool,hashes,theoretical_rewards,paid_rewards,amount_minted,\
+         exhausted_busses,first_exhausted_minute\n",
+    );
+    for r in records {
+        csv += &format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            r.epoch,
+            r.ended_at,
+            r.hashpower,
+            r.miners,
+            r.supply,
+            r.base_reward_rate,
+            r.min_difficulty,
+            r.target_emissions_rate,
+            r.reward_pool,
+            r.hashes,
+            r.theoretical_rewards,
+            r.paid_rewards,
+            r.amount_minted,
+            r.exhausted_busses,
+            r.first_exhausted_minute
+                .map_or(String::new(), |m| m.to_string()),
+        );
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_funded_on_start() {
+        let sim = Simulator::new(0).unwrap();
+        let target_epoch_rewards =
+            sim.config.target_emmissions_rate * sim.config.epoch_minutes as u64;
+        assert!(target_epoch_rewards > 0);
+        assert!(sim.busses.iter().all(|b| b.rewards <= target_epoch_rewards));
+        assert_eq!(
+            sim.supply,
+            target_epoch_rewards * BUS_COUNT as u64 + sim.config.reset_reward
+        );
+
+        // The crank reward is minted on top of the top ups.
+        let bus_rewards: u64 = sim.busses.iter().map(|b| b.rewards).sum();
+        assert_eq!(bus_rewards + sim.config.reset_reward, sim.supply);
+    }
+
+    #[test]
+    fn test_difficulty_rises_with_hashpower() {
+        let mut sim = Simulator::new(0).unwrap();
+        let records = sim.run(&step(200, 100_000.0, 100, 100, 64.0)).unwrap();
+
+        // Once the network settles, a 64x jump in hashpower is absorbed by several bits of
+        // difficulty.
+        let before = records[99].min_difficulty;
+        let after = records[199].min_difficulty;
+        assert!(after >= before + 3, "{} -> {}", before, after);
+
+        // The supply grows by what each reset mints.
+        for pair in records.windows(2) {
+            assert_eq!(pair[1].supply, pair[0].supply + pair[1].amount_minted);
+        }
+    }
+
+    #[test]
+    fn test_ema_smooths_reward_rate() {
+        let trace = step(400, 100_000.0, 100, 200, 64.0);
+        let threshold = summarize(&Simulator::new(0).unwrap().run(&trace).unwrap(), 100);
+        let mut sim = Simulator::new(0).unwrap();
+        sim.set_retarget_mode(RetargetMode::Ema, INITIAL_EMA_WINDOW);
+        let ema = summarize(&sim.run(&trace).unwrap(), 100);
+
+        // The ema mode absorbs the same jump in hashpower with smaller reward rate swings.
+        assert_eq!(threshold.epochs, 300);
+        assert!(
+            ema.mean_rate_swing < threshold.mean_rate_swing,
+            "{} >= {}",
+            ema.mean_rate_swing,
+            threshold.mean_rate_swing
+        );
+    }
+
+    #[test]
+    fn test_referrals_reduce_taxes() {
+        let fees = |sim: &Simulator| {
+            let taxes: u64 = sim.busses.iter().map(|b| b.taxes).sum();
+            let fines: u64 = sim.busses.iter().map(|b| b.fines).sum();
+            (taxes, fines)
+        };
+        let input = EpochInput {
+            hashpower: 100_000.0,
+            miners: 100,
+        };
+        let mut sim = Simulator::new(0).unwrap();
+        sim.mine(&input);
+        let (taxes, fines) = fees(&sim);
+        assert!(taxes > 0);
+        assert_eq!(sim.config.taxes_percent, sim.config.fines_percent);
+        assert_eq!(taxes, fines);
+
+        // Referred proofs pay the referrer's share of taxes directly, so it never accrues on the
+        // busses.
+        let mut sim = Simulator::new(0).unwrap();
+        sim.referred_percent = 100;
+        sim.mine(&input);
+        let (taxes, fines) = fees(&sim);
+        let referral_rewards = fines - taxes;
+        assert!(referral_rewards > 0);
+        assert!(
+            referral_rewards
+                .abs_diff(fines * sim.config.referral_percent / FEE_PERCENT_DENOMINATOR)
+                < sim.busses.iter().map(|b| b.hashes).sum::<u64>()
+        );
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let trace = ramp(20, 1_000.0, 10, 1.1);
+        let a = Simulator::new(7).unwrap().run(&trace).unwrap();
+        let b = Simulator::new(7).unwrap().run(&trace).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(to_csv(&a).lines().count(), 21);
+    }
+}
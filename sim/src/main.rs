@@ -0,0 +1,192 @@
+use std::{fs, process::exit, str::FromStr};
+
+use gary_api::{consts::INITIAL_EMA_WINDOW, state::RetargetMode};
+use gary_sim::{
+    constant, parse_csv, ramp, step, summarize, to_csv, EpochInput, EpochRecord, Simulator,
+};
+
+const USAGE: &str = "\
+Usage: gary-sim [options]
+
+Simulates the network from a newly initialized program and prints a time series of every epoch.
+
+Options:
+  --epochs <n>          Number of epochs to simulate [default: 1000]
+  --hashpower <h>       Network hashpower in hashes per second [default: 100000]
+  --miners <n>          Number of miners splitting the hashpower [default: 100]
+  --step-at <epoch>     Multiply the hashpower by --step-factor from this epoch onwards
+  --step-factor <f>     The hashpower multiplier of --step-at [default: 2]
+  --growth <f>          Multiply the hashpower by this factor every epoch
+  --trace <file>        Read a hashpower,miners row per epoch from a CSV file instead
+  --seed <n>            Seed of the hash difficulty sampling [default: 0]
+  --retarget <mode>     Retarget mode, threshold or ema [default: threshold]
+  --ema-window <n>      Number of epochs averaged by the ema retarget mode [default: 8]
+  --referred <percent>  Percent of hashes submitted by referred proofs [default: 0]
+  --compare             Print the payout stability of both retarget modes instead
+  --warmup <n>          Number of epochs --compare skips while the network settles [default: 100]
+  --json                Print JSON instead of CSV";
+
+struct Args {
+    epochs: usize,
+    hashpower: f64,
+    miners: u64,
+    step_at: Option<usize>,
+    step_factor: f64,
+    growth: Option<f64>,
+    trace: Option<String>,
+    seed: u64,
+    retarget: RetargetMode,
+    ema_window: u64,
+    referred: u64,
+    compare: bool,
+    warmup: usize,
+    json: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        epochs: 1000,
+        hashpower: 100_000.0,
+        miners: 100,
+        step_at: None,
+        step_factor: 2.0,
+        growth: None,
+        trace: None,
+        seed: 0,
+        retarget: RetargetMode::Threshold,
+        ema_window: INITIAL_EMA_WINDOW,
+        referred: 0,
+        compare: false,
+        warmup: 100,
+        json: false,
+    };
+    let mut argv = std::env::args().skip(1);
+    while let Some(flag) = argv.next() {
+        if flag == "--json" {
+            args.json = true;
+            continue;
+        }
+        if flag == "--compare" {
+            args.compare = true;
+            continue;
+        }
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            exit(0);
+        }
+        let value = argv.next().ok_or(format!("missing value for {}", flag))?;
+        match flag.as_str() {
+            "--epochs" => args.epochs = parse(&flag, &value)?,
+            "--hashpower" => args.hashpower = parse(&flag, &value)?,
+            "--miners" => args.miners = parse(&flag, &value)?,
+            "--step-at" => args.step_at = Some(parse(&flag, &value)?),
+            "--step-factor" => args.step_factor = parse(&flag, &value)?,
+            "--growth" => args.growth = Some(parse(&flag, &value)?),
+            "--trace" => args.trace = Some(value),
+            "--seed" => args.seed = parse(&flag, &value)?,
+            "--retarget" => {
+                args.retarget = match value.as_str() {
+                    "threshold" => RetargetMode::Threshold,
+                    "ema" => RetargetMode::Ema,
+                    _ => return Err(format!("invalid value for {}: {}", flag, value)),
+                }
+            }
+            "--ema-window" => args.ema_window = parse(&flag, &value)?,
+            "--referred" => args.referred = parse(&flag, &value)?,
+            "--warmup" => args.warmup = parse(&flag, &value)?,
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+    Ok(args)
+}
+
+fn parse<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn build_trace(args: &Args) -> Result<Vec<EpochInput>, String> {
+    if let Some(path) = &args.trace {
+        let csv = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+        return parse_csv(&csv);
+    }
+    Ok(match (args.step_at, args.growth) {
+        (Some(_), Some(_)) => return Err("--step-at and --growth are exclusive".to_string()),
+        (Some(at), None) => step(
+            args.epochs,
+            args.hashpower,
+            args.miners,
+            at,
+            args.step_factor,
+        ),
+        (None, Some(growth)) => ramp(args.epochs, args.hashpower, args.miners, growth),
+        (None, None) => constant(args.epochs, args.hashpower, args.miners),
+    })
+}
+
+fn simulate(args: &Args, trace: &[EpochInput], mode: RetargetMode) -> Vec<EpochRecord> {
+    Simulator::new(args.seed)
+        .and_then(|mut sim| {
+            sim.set_retarget_mode(mode, args.ema_window);
+            sim.referred_percent = args.referred;
+            sim.run(trace)
+        })
+        .unwrap_or_else(|err| {
+            eprintln!("simulation failed: {}", err);
+            exit(1);
+        })
+}
+
+/// Simulates the trace under both retarget modes and prints how stable their payouts are.
+fn compare(args: &Args, trace: &[EpochInput]) {
+    let modes = [
+        ("threshold", RetargetMode::Threshold),
+        ("ema", RetargetMode::Ema),
+    ];
+    let summaries: Vec<_> = modes
+        .iter()
+        .map(|(name, mode)| (*name, summarize(&simulate(args, trace, *mode), args.warmup)))
+        .collect();
+    if args.json {
+        let summaries: serde_json::Map<_, _> = summaries
+            .iter()
+            .map(|(name, summary)| (name.to_string(), serde_json::to_value(summary).unwrap()))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&summaries).unwrap());
+        return;
+    }
+    println!("mode,epochs,mean_paid_rewards,paid_rewards_cv,mean_rate_swing,difficulty_changes");
+    for (name, s) in summaries {
+        println!(
+            "{},{},{},{},{},{}",
+            name,
+            s.epochs,
+            s.mean_paid_rewards,
+            s.paid_rewards_cv,
+            s.mean_rate_swing,
+            s.difficulty_changes
+        );
+    }
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|err| {
+        eprintln!("{}\n\n{}", err, USAGE);
+        exit(2);
+    });
+    let trace = build_trace(&args).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(2);
+    });
+    if args.compare {
+        compare(&args, &trace);
+        return;
+    }
+    let records = simulate(&args, &trace, args.retarget);
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&records).unwrap());
+    } else {
+        print!("{}", to_csv(&records));
+    }
+}
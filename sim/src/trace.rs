@@ -0,0 +1,80 @@
+use crate::EpochInput;
+
+/// A network with constant hashpower and miners.
+pub fn constant(epochs: usize, hashpower: f64, miners: u64) -> Vec<EpochInput> {
+    vec![EpochInput { hashpower, miners }; epochs]
+}
+
+/// A network whose hashpower is multiplied by `factor` from epoch `at` onwards.
+pub fn step(epochs: usize, hashpower: f64, miners: u64, at: usize, factor: f64) -> Vec<EpochInput> {
+    (0..epochs)
+        .map(|i| EpochInput {
+            hashpower: if i < at {
+                hashpower
+            } else {
+                hashpower * factor
+            },
+            miners,
+        })
+        .collect()
+}
+
+/// A network whose hashpower grows by `growth` every epoch.
+pub fn ramp(epochs: usize, hashpower: f64, miners: u64, growth: f64) -> Vec<EpochInput> {
+    (0..epochs)
+        .map(|i| EpochInput {
+            hashpower: hashpower * growth.powi(i as i32),
+            miners,
+        })
+        .collect()
+}
+
+/// Parses a trace from CSV with a `hashpower,miners` row per epoch. A header row is skipped.
+pub fn parse_csv(csv: &str) -> Result<Vec<EpochInput>, String> {
+    let mut trace = vec![];
+    for (i, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line.starts_with("hashpower")) {
+            continue;
+        }
+        let (hashpower, miners) = line
+            .split_once(',')
+            .ok_or(format!("line {}: expected hashpower,miners", i + 1))?;
+        trace.push(EpochInput {
+            hashpower: hashpower
+                .trim()
+                .parse()
+                .map_err(|err| format!("line {}: invalid hashpower: {}", i + 1, err))?,
+            miners: miners
+                .trim()
+                .parse()
+                .map_err(|err| format!("line {}: invalid miners: {}", i + 1, err))?,
+        });
+    }
+    Ok(trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let trace = parse_csv("hashpower,miners\n1000,10\n2e3, 20\n\n").unwrap();
+        assert_eq!(
+            trace,
+            vec![
+                EpochInput {
+                    hashpower: 1000.0,
+                    miners: 10
+                },
+                EpochInput {
+                    hashpower: 2000.0,
+                    miners: 20
+                },
+            ]
+        );
+        assert!(parse_csv("1000").is_err());
+        assert!(parse_csv("1000,ten").is_err());
+    }
+}